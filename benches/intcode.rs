@@ -0,0 +1,31 @@
+#![feature(test)]
+extern crate aoc2019;
+extern crate test;
+
+use aoc2019::intcode::IntCodeComputer;
+use test::Bencher;
+
+// day9 part 2 runs BOOST in sensor boost mode, a few hundred thousand instructions
+fn boost() -> IntCodeComputer {
+    let proggy = include_str!("../input/2019/day9.txt")
+        .trim()
+        .split(",")
+        .map(|s| s.to_owned())
+        .collect();
+    let mut icc = IntCodeComputer::new(proggy);
+    icc.queue_input(2);
+    icc
+}
+
+#[bench]
+fn boost_with_decode_cache(b: &mut Bencher) {
+    let icc = boost();
+    b.iter(|| icc.clone().run_until_halt());
+}
+
+#[bench]
+fn boost_plain_interpreter(b: &mut Bencher) {
+    let mut icc = boost();
+    icc.disable_decode_cache();
+    b.iter(|| icc.clone().run_until_halt());
+}
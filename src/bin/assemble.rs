@@ -0,0 +1,25 @@
+extern crate aoc2019;
+
+use aoc2019::intcode::assemble::assemble;
+use itertools::Itertools;
+use std::io::Read;
+
+// usage: assemble [path to source], reads the source from stdin if there's no path. prints the
+// program as comma separated ints, the same format as the puzzle inputs
+fn main() {
+    let source = match std::env::args().nth(1) {
+        Some(path) => std::fs::read_to_string(path).unwrap(),
+        None => {
+            let mut source = String::new();
+            std::io::stdin().read_to_string(&mut source).unwrap();
+            source
+        }
+    };
+    match assemble(&source) {
+        Ok(proggy) => println!("{}", proggy.iter().join(",")),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
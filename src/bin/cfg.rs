@@ -0,0 +1,20 @@
+extern crate aoc2019;
+
+use aoc2019::intcode::cfg::control_flow_graph;
+use aoc2019::intcode::parse_proggy;
+use std::io::Read;
+
+// usage: cfg [path to program], reads the program from stdin if there's no path. prints the
+// control flow graph in graphviz's dot format, e.g. `cfg input/2019/day9.txt | dot -Tsvg`
+fn main() {
+    let input = match std::env::args().nth(1) {
+        Some(path) => std::fs::read_to_string(path).unwrap(),
+        None => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input).unwrap();
+            input
+        }
+    };
+    let proggy = parse_proggy(&input);
+    println!("{}", control_flow_graph(&proggy).to_dot(&proggy));
+}
@@ -0,0 +1,39 @@
+extern crate aoc2019;
+
+use aoc2019::intcode::debugger::Debugger;
+use aoc2019::intcode::{parse_proggy, IntCodeComputer};
+use std::io::{BufRead, Write};
+
+// usage: debug <path to program>, then type debugger commands, `help` lists them. an empty line
+// repeats the last command
+fn main() {
+    let path = std::env::args()
+        .nth(1)
+        .expect("usage: debug <path to program>");
+    let proggy = parse_proggy(&std::fs::read_to_string(path).unwrap());
+    let mut debugger = Debugger::new(IntCodeComputer::from_ints(proggy));
+    println!("{}", debugger.registers());
+
+    let stdin = std::io::stdin();
+    let mut last_command = String::new();
+    loop {
+        print!("(icd) ");
+        std::io::stdout().flush().unwrap();
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap() == 0 {
+            break;
+        }
+        let line = line.trim();
+        if line == "q" || line == "quit" {
+            break;
+        }
+        if !line.is_empty() {
+            last_command = line.to_owned();
+        }
+        match debugger.command(&last_command) {
+            Ok(output) if output.is_empty() => (),
+            Ok(output) => println!("{}", output),
+            Err(e) => println!("error: {}", e),
+        }
+    }
+}
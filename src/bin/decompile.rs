@@ -0,0 +1,23 @@
+extern crate aoc2019;
+
+use aoc2019::intcode::decompile::decompile;
+use aoc2019::intcode::parse_proggy;
+use std::io::Read;
+
+// usage: decompile [path to program], reads the program from stdin if there's no path
+fn main() {
+    let input = match std::env::args().nth(1) {
+        Some(path) => std::fs::read_to_string(path).unwrap(),
+        None => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input).unwrap();
+            input
+        }
+    };
+    // nothing at all is an empty program, which decompiles to a main that isn't code
+    let proggy = match input.trim() {
+        "" => vec![],
+        input => parse_proggy(input),
+    };
+    println!("{}", decompile(&proggy));
+}
@@ -0,0 +1,18 @@
+extern crate aoc2019;
+
+use aoc2019::intcode::disassemble::disassemble;
+use aoc2019::intcode::parse_proggy;
+use std::io::Read;
+
+// usage: disassemble [path to program], reads the program from stdin if there's no path
+fn main() {
+    let input = match std::env::args().nth(1) {
+        Some(path) => std::fs::read_to_string(path).unwrap(),
+        None => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input).unwrap();
+            input
+        }
+    };
+    println!("{}", disassemble(&parse_proggy(&input)));
+}
@@ -0,0 +1,59 @@
+extern crate aoc2019;
+
+use aoc2019::intcode::{parse_proggy, IntCodeComputer};
+use itertools::Itertools;
+
+const USAGE: &str = "usage: profile <path to program> [--input 1,2,3] [--ascii <path to text \
+                     to send as input>] [--top <n>] [--collapsed <path to write stacks to>] \
+                     [--max-steps <n>]";
+
+// runs the program until it halts, runs out of input or uses up its steps, then prints where the
+// time went
+fn main() {
+    let args = std::env::args().collect_vec();
+    let path = args.get(1).unwrap_or_else(|| {
+        eprintln!("{}", USAGE);
+        std::process::exit(1)
+    });
+    let mut icc = IntCodeComputer::from_ints(parse_proggy(&std::fs::read_to_string(path).unwrap()));
+    let mut top = 20;
+    let mut collapsed = None;
+    for flag_and_value in args[2..].chunks(2) {
+        let (flag, value) = match flag_and_value {
+            [flag, value] => (flag, value),
+            // a flag on the end with nothing after it
+            _ => {
+                eprintln!("{}", USAGE);
+                std::process::exit(1);
+            }
+        };
+        match flag.as_str() {
+            "--input" => {
+                for input in value.split(',') {
+                    icc.queue_input(input.trim().parse().unwrap());
+                }
+            }
+            "--ascii" => {
+                for chr in std::fs::read_to_string(value).unwrap().chars() {
+                    icc.queue_input(chr as i128);
+                }
+            }
+            "--top" => top = value.parse().unwrap(),
+            "--collapsed" => collapsed = Some(value),
+            "--max-steps" => icc.set_limits(icc.limits().steps(value.parse().unwrap())),
+            _ => {
+                eprintln!("{}", USAGE);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let profiler = icc.start_profiling();
+    let (_, result) = icc.run_and_collect_all_output();
+    let profiler = profiler.lock().unwrap();
+    println!("stopped with {:?}\n", result);
+    println!("{}", profiler.report(top));
+    if let Some(path) = collapsed {
+        std::fs::write(path, profiler.collapsed_stacks() + "\n").unwrap();
+    }
+}
@@ -0,0 +1,30 @@
+extern crate aoc2019;
+
+use aoc2019::intcode::{parse_proggy, IntCodeComputer};
+use itertools::Itertools;
+
+// usage: trace <path to program> <path to write the trace to> [input, ...]
+// runs the program until it halts with the given input, printing whatever it outputs, and writes
+// a json lines trace of every instruction it ran
+fn main() {
+    let args = std::env::args().collect_vec();
+    if args.len() < 3 {
+        eprintln!("usage: trace <path to program> <path to write the trace to> [input, ...]");
+        std::process::exit(1);
+    }
+    let proggy = parse_proggy(&std::fs::read_to_string(&args[1]).unwrap());
+    let mut icc = IntCodeComputer::from_ints(proggy);
+    icc.trace_json_lines(&args[2]).unwrap();
+    for input in args[3..].iter().flat_map(|arg| arg.split(',')) {
+        icc.queue_input(input.trim().parse().unwrap());
+    }
+    let result = icc.try_run_until_halt();
+    icc.stop_tracing();
+    match result {
+        Ok(output) => println!("{}", output.iter().join(",")),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,20 @@
+extern crate aoc2019;
+
+use aoc2019::intcode::parse_proggy;
+use aoc2019::intcode::transpile::transpile;
+use std::io::Read;
+
+// usage: transpile [path to program], reads the program from stdin if there's no path. prints a
+// rust module that runs the program, e.g.
+// `transpile input/2019/day9.txt > src/intcode/transpile/day9_boost.rs`
+fn main() {
+    let input = match std::env::args().nth(1) {
+        Some(path) => std::fs::read_to_string(path).unwrap(),
+        None => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input).unwrap();
+            input
+        }
+    };
+    print!("{}", transpile(&parse_proggy(&input)));
+}
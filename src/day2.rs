@@ -1,46 +1,36 @@
-fn run_proggy(mut proggy: Vec<usize>, noun: usize, verb: usize) -> usize {
-    proggy[1] = noun;
-    proggy[2] = verb;
-    let mut current_pos = 0;
-    loop {
-        match proggy[current_pos] {
-            1 => {
-                let s1 = proggy[current_pos + 1];
-                let s2 = proggy[current_pos + 2];
-                let dest = proggy[current_pos + 3];
-                proggy[dest] = proggy[s1] + proggy[s2];
-                current_pos += 4;
-            },
-            2 => {
-                let s1 = proggy[current_pos + 1];
-                let s2 = proggy[current_pos + 2];
-                let dest = proggy[current_pos + 3];
-                proggy[dest] = proggy[s1] * proggy[s2];
-                current_pos += 4;
-            },
-            99 => return proggy[0],
-            _ => panic!("invalid operation"),
-        }
-    }
+use crate::intcode::IntCodeComputer;
+
+// the machine's default Arithmetic::Checked makes a noun and verb that overflow fail loudly
+// instead of giving back garbage
+fn run_proggy(icc: &IntCodeComputer, noun: i128, verb: i128) -> i128 {
+    let mut icc = icc.clone();
+    icc.write_memory(1, noun);
+    icc.write_memory(2, verb);
+    icc.run_until_halt();
+    icc.read_memory(0)
 }
 
 #[aoc(day2, part1)]
-pub fn solve_part1(input: &str) -> usize {
-    let proggy : Vec<usize> = input.lines().nth(0).unwrap().split(',')
+pub fn solve_part1(input: &str) -> i128 {
+    let proggy : Vec<i128> = input.lines().next().unwrap().split(',')
         .map(|opcode_s| opcode_s.parse().unwrap()).collect();
 
     // 1202 protocol
-    run_proggy(proggy, 12, 2)
+    run_proggy(&IntCodeComputer::from_ints(proggy), 12, 2)
 }
 
 #[aoc(day2, part2)]
-pub fn solve_part2(input: &str) -> usize {
-    let proggy : Vec<usize> = input.lines().nth(0).unwrap().split(',')
+pub fn solve_part2(input: &str) -> i128 {
+    let proggy : Vec<i128> = input.lines().next().unwrap().split(',')
         .map(|opcode_s| opcode_s.parse().unwrap()).collect();
 
+    // every try writes over the operands it's about to run, so a clone would throw away most of
+    // the decode cache it fills in. it's cheaper not to have one
+    let mut icc = IntCodeComputer::from_ints(proggy);
+    icc.disable_decode_cache();
     for noun in 0..100 {
         for verb in 0..100 {
-            let output = run_proggy(proggy.clone(), noun, verb);
+            let output = run_proggy(&icc, noun, verb);
             if output == 19690720 {
                 return (100 * noun) + verb
             }
@@ -1,413 +1,368 @@
-use crate::day23::Instruction::{
-    Add1, Equals8, Halt99, Input3, JumpIfFalse6, JumpIfTrue5, LessThan7, Multiply2, Output4,
-    RelativeBaseOffset9,
-};
-use crate::day23::ParameterMode::{ImmediateMode1, PositionMode0, RelativeMode2};
-use defaultmap::DefaultHashMap;
-use itertools::Itertools;
-use std::collections::VecDeque;
+use crate::intcode::network::{Action, Monitor, Network, NetworkError, Node, Route};
+use crate::intcode::{IntCodeComputer, IntcodeError, RunResult};
+use crossbeam::channel::{unbounded, Receiver, Sender, TryRecvError};
+use std::convert::TryFrom;
+use std::fmt;
+use std::sync::{Condvar, Mutex};
 
-#[derive(Debug)]
-enum Instruction {
-    Add1(ParameterMode, ParameterMode, ParameterMode),
-    Multiply2(ParameterMode, ParameterMode, ParameterMode),
-    Input3(ParameterMode),
-    Output4(ParameterMode),
-    JumpIfTrue5(ParameterMode, ParameterMode),
-    JumpIfFalse6(ParameterMode, ParameterMode),
-    LessThan7(ParameterMode, ParameterMode, ParameterMode),
-    Equals8(ParameterMode, ParameterMode, ParameterMode),
-    RelativeBaseOffset9(ParameterMode),
-    Halt99,
-}
+const NUM_COMPUTERS: usize = 50;
 
-impl Instruction {
-    fn parse(s: &str) -> Self {
-        let parsed = if s.ends_with("1") {
-            let s = format!("{:0>5}", s);
-            let third_param_mode = ParameterMode::parse(s.chars().nth(0).unwrap());
-            let second_param_mode = ParameterMode::parse(s.chars().nth(1).unwrap());
-            let first_param_mode = ParameterMode::parse(s.chars().nth(2).unwrap());
-            Add1(first_param_mode, second_param_mode, third_param_mode)
-        } else if s.ends_with("2") {
-            let s = format!("{:0>5}", s);
-            let third_param_mode = ParameterMode::parse(s.chars().nth(0).unwrap());
-            let second_param_mode = ParameterMode::parse(s.chars().nth(1).unwrap());
-            let first_param_mode = ParameterMode::parse(s.chars().nth(2).unwrap());
-            Multiply2(first_param_mode, second_param_mode, third_param_mode)
-        } else if s.ends_with("3") {
-            let s = format!("{:0>3}", s);
-            let first_param_mode = ParameterMode::parse(s.chars().nth(0).unwrap());
-            Input3(first_param_mode)
-        } else if s.ends_with("4") {
-            let s = format!("{:0>3}", s);
-            let first_param_mode = ParameterMode::parse(s.chars().nth(0).unwrap());
-            Output4(first_param_mode)
-        } else if s.ends_with("5") {
-            let s = format!("{:0>4}", s);
-            let second_param_mode = ParameterMode::parse(s.chars().nth(0).unwrap());
-            let first_param_mode = ParameterMode::parse(s.chars().nth(1).unwrap());
-            JumpIfTrue5(first_param_mode, second_param_mode)
-        } else if s.ends_with("6") {
-            let s = format!("{:0>4}", s);
-            let second_param_mode = ParameterMode::parse(s.chars().nth(0).unwrap());
-            let first_param_mode = ParameterMode::parse(s.chars().nth(1).unwrap());
-            JumpIfFalse6(first_param_mode, second_param_mode)
-        } else if s.ends_with("7") {
-            let s = format!("{:0>5}", s);
-            let third_param_mode = ParameterMode::parse(s.chars().nth(0).unwrap());
-            let second_param_mode = ParameterMode::parse(s.chars().nth(1).unwrap());
-            let first_param_mode = ParameterMode::parse(s.chars().nth(2).unwrap());
-            LessThan7(first_param_mode, second_param_mode, third_param_mode)
-        } else if s.ends_with("8") {
-            let s = format!("{:0>5}", s);
-            let third_param_mode = ParameterMode::parse(s.chars().nth(0).unwrap());
-            let second_param_mode = ParameterMode::parse(s.chars().nth(1).unwrap());
-            let first_param_mode = ParameterMode::parse(s.chars().nth(2).unwrap());
-            Equals8(first_param_mode, second_param_mode, third_param_mode)
-        } else if s == "99" {
-            Halt99
-        } else if s.ends_with("9") {
-            let s = format!("{:0>3}", s);
-            let first_param_mode = ParameterMode::parse(s.chars().nth(0).unwrap());
-            RelativeBaseOffset9(first_param_mode)
-        } else {
-            panic!(format!("unable to parse instruction {}", s))
-        };
-        parsed
-    }
+#[derive(Clone, Copy)]
+enum Until {
+    // part 1
+    FirstPacketToNat,
+    // part 2, the first y the nat delivers twice in a row
+    NatRepeatsItself,
 }
 
-#[derive(Debug)]
-enum ParameterMode {
-    PositionMode0,
-    ImmediateMode1,
-    RelativeMode2,
+#[derive(Debug, PartialEq)]
+enum NoAnswer {
+    // the network went idle without anything reaching the nat, so nothing ever will
+    NothingReachedTheNat,
+    Failed(NetworkError),
 }
 
-impl ParameterMode {
-    fn parse(c: char) -> Self {
-        match c {
-            '0' => PositionMode0,
-            '1' => ImmediateMode1,
-            '2' => RelativeMode2,
-            _ => panic!(format!("unable to parse param mode {:?}", c)),
+impl fmt::Display for NoAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NoAnswer::NothingReachedTheNat => write!(f, "nothing ever reached the nat"),
+            NoAnswer::Failed(e) => write!(f, "{}", e),
         }
     }
 }
 
-fn get_first_param(
-    proggy: &Proggy,
-    instruction_pos: usize,
-    mode: ParameterMode,
-    relative_base: i128,
-) -> i128 {
-    let i = proggy[instruction_pos + 1].parse().unwrap();
-    match mode {
-        PositionMode0 => proggy[i as usize].parse().unwrap(),
-        ImmediateMode1 => i,
-        RelativeMode2 => proggy[(i + relative_base) as usize]
-            .parse::<i128>()
-            .unwrap(),
-    }
+impl std::error::Error for NoAnswer {}
+
+// what the computers on their own threads all keep track of together, so the nat can tell
+// when the network's gone idle
+#[derive(Default)]
+struct Traffic {
+    // computers that are blocked waiting for a packet, or halted
+    waiting: usize,
+    // packets that have been sent but not picked up yet
+    in_flight: usize,
+    to_nat: Vec<(i128, i128)>,
+    // the first computer that failed, which stops everything
+    failed: Option<NetworkError>,
 }
 
-fn get_second_param(
-    proggy: &Proggy,
-    instruction_pos: usize,
-    mode: ParameterMode,
-    relative_base: i128,
-) -> i128 {
-    let i = proggy[instruction_pos + 2].parse().unwrap();
-    match mode {
-        PositionMode0 => proggy[i as usize].parse().unwrap(),
-        ImmediateMode1 => i,
-        RelativeMode2 => proggy[(i + relative_base) as usize]
-            .parse::<i128>()
-            .unwrap(),
-    }
+struct ThreadedNetwork {
+    computers: Vec<Sender<(i128, i128)>>,
+    traffic: Mutex<Traffic>,
+    // rung whenever something the nat's waiting on might have happened
+    changed: Condvar,
 }
 
-fn get_third_param(
-    proggy: &Proggy,
-    instruction_pos: usize,
-    mode: ParameterMode,
-    relative_base: i128,
-) -> i128 {
-    match mode {
-        PositionMode0 => proggy[instruction_pos + 3].parse().unwrap(),
-        ImmediateMode1 => panic!("invalid program, third param can't be immediate mode"),
-        RelativeMode2 => proggy[instruction_pos + 3].parse::<i128>().unwrap() + relative_base,
+impl ThreadedNetwork {
+    // packets for an address that's negative or has no computer at it are dropped, and so are
+    // ones for a computer that's failed
+    fn send(&self, dest: i128, x: i128, y: i128) {
+        let mut traffic = self.traffic.lock().unwrap();
+        if dest == 255 {
+            traffic.to_nat.push((x, y));
+            self.changed.notify_one();
+            return;
+        }
+        let computer = usize::try_from(dest)
+            .ok()
+            .and_then(|dest| self.computers.get(dest));
+        if let Some(computer) = computer {
+            if computer.send((x, y)).is_ok() {
+                traffic.in_flight += 1;
+            }
+        }
     }
-}
 
-type Proggy = DefaultHashMap<usize, String>;
+    fn fail(&self, node: usize, error: IntcodeError) {
+        let mut traffic = self.traffic.lock().unwrap();
+        traffic.failed.get_or_insert(NetworkError { node, error });
+        self.changed.notify_one();
+    }
 
-#[derive(Clone)]
-struct IntCodeComputer {
-    num_instructions_processed: usize,
-    proggy: Proggy,
-    input: VecDeque<i128>,
-    current_pos: usize,
-    relative_base: i128,
+    fn is_idle(traffic: &Traffic) -> bool {
+        traffic.waiting == NUM_COMPUTERS && traffic.in_flight == 0
+    }
 }
 
-#[derive(Debug)]
-enum RunResult {
-    NeedMoreInput,
-    Output(i128),
-    Halt,
+// a computer on its own thread. a -1 is what tells it there's no packet, but once one's come and
+// gone without it sending anything, more of them won't either, so after that it blocks until a
+// packet turns up instead of spinning. if it fails it tells the nat and its thread ends
+fn run_computer(
+    addr: usize,
+    mut icc: IntCodeComputer,
+    packets: Receiver<(i128, i128)>,
+    shutdown: Receiver<()>,
+    network: &ThreadedNetwork,
+) {
+    // -1s given since the computer last sent or got a packet
+    let mut quiet = 0;
+    let mut unsent = vec![];
+    loop {
+        match icc.run_and_get_next() {
+            RunResult::Output(value) => {
+                unsent.push(value);
+                if let [dest, x, y] = unsent[..] {
+                    network.send(dest, x, y);
+                    unsent.clear();
+                    quiet = 0;
+                }
+            }
+            RunResult::Halt => return drop_packets(packets, shutdown, network),
+            RunResult::Error(error) => return network.fail(addr, error),
+            RunResult::NeedMoreInput => {
+                if let Err(TryRecvError::Disconnected) = shutdown.try_recv() {
+                    return;
+                }
+                let packet = match packets.try_recv() {
+                    Ok(packet) => {
+                        network.traffic.lock().unwrap().in_flight -= 1;
+                        packet
+                    }
+                    Err(_) if quiet == 0 => {
+                        quiet += 1;
+                        icc.queue_input(-1);
+                        continue;
+                    }
+                    Err(_) => {
+                        network.traffic.lock().unwrap().waiting += 1;
+                        network.changed.notify_one();
+                        crossbeam::select! {
+                            recv(packets) -> packet => {
+                                let mut traffic = network.traffic.lock().unwrap();
+                                traffic.waiting -= 1;
+                                traffic.in_flight -= 1;
+                                packet.unwrap()
+                            }
+                            recv(shutdown) -> _ => return,
+                        }
+                    }
+                };
+                icc.queue_input(packet.0);
+                icc.queue_input(packet.1);
+                quiet = 0;
+            }
+        }
+    }
 }
 
-impl IntCodeComputer {
-    fn new(proggy: Vec<String>) -> Self {
-        let proggy =
-            DefaultHashMap::new_with_map("0".to_owned(), proggy.into_iter().enumerate().collect());
-        IntCodeComputer {
-            proggy,
-            input: VecDeque::new(),
-            current_pos: 0,
-            relative_base: 0,
-            num_instructions_processed: 0,
+// a halted computer won't take another packet, but it counts as waiting so the network can still
+// go idle, and whatever's sent to it gets picked up and thrown away
+fn drop_packets(
+    packets: Receiver<(i128, i128)>,
+    shutdown: Receiver<()>,
+    network: &ThreadedNetwork,
+) {
+    network.traffic.lock().unwrap().waiting += 1;
+    network.changed.notify_one();
+    loop {
+        crossbeam::select! {
+            recv(packets) -> _ => {
+                network.traffic.lock().unwrap().in_flight -= 1;
+                network.changed.notify_one();
+            }
+            recv(shutdown) -> _ => return,
         }
     }
+}
 
-    fn queue_input(&mut self, input: i128) {
-        self.input.push_front(input);
-    }
+// every computer gets a thread of its own and the nat runs on this one. the threads all stop
+// once the nat has its answer, once a computer fails, or once the network's gone idle without
+// anything reaching the nat, which means nothing ever will
+fn run_threaded(proggy: &[String], until: Until) -> Result<i128, NoAnswer> {
+    let (computers, inboxes): (Vec<_>, Vec<_>) = (0..NUM_COMPUTERS).map(|_| unbounded()).unzip();
+    let network = ThreadedNetwork {
+        computers,
+        traffic: Mutex::new(Traffic::default()),
+        changed: Condvar::new(),
+    };
+    let (stop, shutdown) = unbounded::<()>();
+    crossbeam::scope(|scope| {
+        for (addr, packets) in inboxes.into_iter().enumerate() {
+            let mut icc = IntCodeComputer::new(proggy.to_vec());
+            icc.queue_input(addr as i128);
+            let shutdown = shutdown.clone();
+            let network = &network;
+            scope.spawn(move |_| run_computer(addr, icc, packets, shutdown, network));
+        }
 
-    #[allow(unused)]
-    fn run_until_halt(&mut self) -> Vec<i128> {
-        let mut all_output = vec![];
-        loop {
-            match self.run_and_get_next() {
-                RunResult::Output(output) => all_output.push(output),
-                RunResult::Halt => break,
-                otherwise => panic!("didn't expect non-output, but got {:?}", otherwise),
+        let mut traffic = network.traffic.lock().unwrap();
+        let mut delivered_by_nat = None;
+        let answer = loop {
+            traffic = network
+                .changed
+                .wait_while(traffic, |traffic| {
+                    traffic.failed.is_none()
+                        && match until {
+                            Until::FirstPacketToNat => {
+                                traffic.to_nat.is_empty() && !ThreadedNetwork::is_idle(traffic)
+                            }
+                            Until::NatRepeatsItself => !ThreadedNetwork::is_idle(traffic),
+                        }
+                })
+                .unwrap();
+            if let Some(e) = traffic.failed.take() {
+                break Err(NoAnswer::Failed(e));
             }
-        }
-        all_output
-    }
+            let (x, y) = match (until, traffic.to_nat.last()) {
+                (_, None) => break Err(NoAnswer::NothingReachedTheNat),
+                (Until::FirstPacketToNat, Some(_)) => break Ok(traffic.to_nat[0].1),
+                (Until::NatRepeatsItself, Some(&packet)) => packet,
+            };
+            if delivered_by_nat == Some(y) {
+                break Ok(y);
+            }
+            delivered_by_nat = Some(y);
+            if network.computers[0].send((x, y)).is_ok() {
+                traffic.in_flight += 1;
+            }
+        };
+        drop(traffic);
+        drop(stop);
+        answer
+    })
+    .unwrap()
+}
+
+// the nat as a monitor at 255, for running the network as it's declared below
+struct Nat {
+    until: Until,
+    packet: Option<(i128, i128)>,
+    delivered_by_nat: Option<i128>,
+}
 
-    #[allow(unused)]
-    fn run_and_collect_all_output(&mut self) -> (Vec<i128>, RunResult) {
-        let mut all_output = vec![];
-        let mut result;
-        loop {
-            result = self.run_and_get_next();
-            match result {
-                RunResult::Output(output) => all_output.push(output),
-                RunResult::NeedMoreInput | RunResult::Halt => break,
+impl Monitor for Nat {
+    fn receive(&mut self, message: &[i128]) -> Action {
+        match self.until {
+            Until::FirstPacketToNat => Action::Stop(message[1]),
+            Until::NatRepeatsItself => {
+                self.packet = Some((message[0], message[1]));
+                Action::Nothing
             }
         }
-        (all_output, result)
     }
 
-    fn run_and_get_next(&mut self) -> RunResult {
-        self.run().next().unwrap()
-    }
-
-    fn get_input_param(&self, mode: ParameterMode) -> usize {
-        let pos = self.get_first_param(ImmediateMode1);
-        match mode {
-            PositionMode0 => pos as usize,
-            ImmediateMode1 => panic!("inputs not allowed to be in immediate mode"),
-            RelativeMode2 => (pos + self.relative_base) as usize,
+    // with nothing to send the network stays idle, and run gives back None
+    fn idle(&mut self) -> Action {
+        let (x, y) = match self.packet {
+            Some(packet) => packet,
+            None => return Action::Nothing,
+        };
+        if self.delivered_by_nat == Some(y) {
+            return Action::Stop(y);
         }
+        self.delivered_by_nat = Some(y);
+        Action::Send(0, vec![x, y])
     }
+}
 
-    fn get_first_param(&self, mode: ParameterMode) -> i128 {
-        get_first_param(&self.proggy, self.current_pos, mode, self.relative_base)
-    }
+// the whole network on one thread, with the computers taking turns. it always runs the same way,
+// so the tests check the threads against it
+fn run_network(proggy: &[String], until: Until) -> Result<i128, NoAnswer> {
+    let network = (0..NUM_COMPUTERS).fold(Network::new(), |network, addr| {
+        network.node(
+            Node::new(IntCodeComputer::new(proggy.to_vec()))
+                .input(&[addr as i128])
+                .route(Route::Packets)
+                .when_empty(-1),
+        )
+    });
+    let nat = Nat {
+        until,
+        packet: None,
+        delivered_by_nat: None,
+    };
+    network
+        .monitor(255, nat)
+        .run()
+        .map_err(NoAnswer::Failed)?
+        .ok_or(NoAnswer::NothingReachedTheNat)
+}
 
-    fn get_second_param(&self, mode: ParameterMode) -> i128 {
-        get_second_param(&self.proggy, self.current_pos, mode, self.relative_base)
-    }
+fn parse(input: &str) -> Vec<String> {
+    input.split(",").map(|s| s.to_owned()).collect()
+}
 
-    fn get_third_param(&self, mode: ParameterMode) -> i128 {
-        get_third_param(&self.proggy, self.current_pos, mode, self.relative_base)
-    }
+#[aoc(day23, part1)]
+fn solve_part1(input: &str) -> Result<i128, NoAnswer> {
+    run_threaded(&parse(input), Until::FirstPacketToNat)
+}
 
-    fn run(&mut self) -> impl Iterator<Item = RunResult> + '_ {
-        std::iter::from_fn(move || loop {
-            let instruction = Instruction::parse(&self.proggy[self.current_pos].to_string());
-            self.num_instructions_processed += 1;
-            match instruction {
-                Add1(first_mode, second_mode, third_mode) => {
-                    let param_1 = self.get_first_param(first_mode);
-                    let param_2 = self.get_second_param(second_mode);
-                    let param_3 = self.get_third_param(third_mode);
-                    self.proggy[param_3 as usize] = (param_1 + param_2).to_string();
-                    self.current_pos += 4;
-                }
-                Multiply2(first_mode, second_mode, third_mode) => {
-                    let param_1 = self.get_first_param(first_mode);
-                    let param_2 = self.get_second_param(second_mode);
-                    let param_3 = self.get_third_param(third_mode);
-                    self.proggy[param_3 as usize] = (param_1 * param_2).to_string();
-                    self.current_pos += 4;
-                }
-                Input3(mode) => {
-                    let raw_position = self.get_input_param(mode);
-                    match self.input.pop_back() {
-                        Some(input) => {
-                            self.proggy[raw_position] = input.to_string();
-                            self.current_pos += 2;
-                        }
-                        None => return Some(RunResult::NeedMoreInput),
-                    }
-                }
-                Output4(mode) => {
-                    let param = self.get_first_param(mode);
-                    self.current_pos += 2;
-                    return Some(RunResult::Output(param));
-                }
-                Halt99 => {
-                    return Some(RunResult::Halt);
-                }
-                JumpIfTrue5(first_mode, second_mode) => {
-                    let param_1 = self.get_first_param(first_mode);
-                    let param_2 = self.get_second_param(second_mode);
-                    if param_1 != 0 {
-                        self.current_pos = param_2 as usize;
-                    } else {
-                        self.current_pos += 3;
-                    }
-                }
-                JumpIfFalse6(first_mode, second_mode) => {
-                    let param_1 = self.get_first_param(first_mode);
-                    let param_2 = self.get_second_param(second_mode);
-                    if param_1 == 0 {
-                        self.current_pos = param_2 as usize;
-                    } else {
-                        self.current_pos += 3;
-                    }
-                }
-                LessThan7(first_mode, second_mode, third_mode) => {
-                    let param_1 = self.get_first_param(first_mode);
-                    let param_2 = self.get_second_param(second_mode);
-                    let param_3 = self.get_third_param(third_mode);
-                    self.proggy[param_3 as usize] = if param_1 < param_2 {
-                        "1".to_owned()
-                    } else {
-                        "0".to_owned()
-                    };
-                    self.current_pos += 4;
-                }
-                Equals8(first_mode, second_mode, third_mode) => {
-                    let param_1 = self.get_first_param(first_mode);
-                    let param_2 = self.get_second_param(second_mode);
-                    let param_3 = self.get_third_param(third_mode);
-                    self.proggy[param_3 as usize] = if param_1 == param_2 {
-                        "1".to_owned()
-                    } else {
-                        "0".to_owned()
-                    };
-                    self.current_pos += 4;
-                }
-                RelativeBaseOffset9(first_mode) => {
-                    let param_1 = self.get_first_param(first_mode);
-                    self.relative_base += param_1 as i128;
-                    self.current_pos += 2;
-                }
-            }
-        })
-    }
+#[aoc(day23, part2)]
+fn solve_part2(input: &str) -> Result<i128, NoAnswer> {
+    run_threaded(&parse(input), Until::NatRepeatsItself)
 }
 
-fn interpret_output(output: Vec<i128>) -> impl Iterator<Item = (usize, i128, i128)> + 'static {
-    output
-        .into_iter()
-        .tuples()
-        .map(|(dest, x, y)| (dest as usize, x, y))
+#[aoc(day23, part1, network)]
+fn solve_part1_network(input: &str) -> Result<i128, NoAnswer> {
+    run_network(&parse(input), Until::FirstPacketToNat)
 }
 
-fn try_to_get_next_input_instruction(
-    computers: &mut [IntCodeComputer],
-) -> Option<impl Iterator<Item = (usize, i128, i128)> + '_> {
-    for computer in computers {
-        computer.queue_input(-1);
-        let (output, _) = computer.run_and_collect_all_output();
-        if output.len() > 0 {
-            return Some(interpret_output(output));
-        }
+#[aoc(day23, part2, network)]
+fn solve_part2_network(input: &str) -> Result<i128, NoAnswer> {
+    run_network(&parse(input), Until::NatRepeatsItself)
+}
+
+#[test]
+fn threads_give_the_same_answers_as_one_thread() {
+    let proggy = parse(include_str!("../input/2019/day23.txt"));
+    for &until in &[Until::FirstPacketToNat, Until::NatRepeatsItself] {
+        let answer = run_network(&proggy, until);
+        assert!(answer.is_ok());
+        assert_eq!(answer, run_threaded(&proggy, until));
     }
-    None
 }
 
-#[aoc(day23, part1)]
-fn solve_part1(input: &str) -> i128 {
-    let proggy: Vec<_> = input.split(",").map(|s| s.to_owned()).collect();
-    let mut computers = (0..50)
-        .map(|addr| {
-            let mut icc = IntCodeComputer::new(proggy.clone());
-            icc.queue_input(addr as i128);
-            icc
-        })
-        .collect_vec();
-    let mut q = VecDeque::new();
-    loop {
-        if q.is_empty() {
-            for packet in try_to_get_next_input_instruction(&mut computers).unwrap() {
-                q.push_back(packet);
-            }
-        } else {
-            let (dest, x, y) = q.pop_back().unwrap();
-            if dest == 255 {
-                return y;
-            }
-            computers[dest].queue_input(x);
-            computers[dest].queue_input(y);
-            let (output, _) = computers[dest].run_and_collect_all_output();
-            for packet in interpret_output(output) {
-                q.push_back(packet);
-            }
-        }
+#[test]
+fn no_answer_if_nothing_ever_reaches_the_nat() {
+    // reads its address and then keeps asking for packets forever
+    let proggy = parse("3,100,3,100,1105,1,2");
+    for &until in &[Until::FirstPacketToNat, Until::NatRepeatsItself] {
+        assert_eq!(
+            Err(NoAnswer::NothingReachedTheNat),
+            run_network(&proggy, until)
+        );
+        assert_eq!(
+            Err(NoAnswer::NothingReachedTheNat),
+            run_threaded(&proggy, until)
+        );
     }
 }
 
-#[aoc(day23, part2)]
-fn solve_part2(input: &str) -> i128 {
-    let proggy: Vec<_> = input.split(",").map(|s| s.to_owned()).collect();
-    let mut computers = (0..50)
-        .map(|addr| {
-            let mut icc = IntCodeComputer::new(proggy.clone());
-            icc.queue_input(addr as i128);
-            icc
-        })
-        .collect_vec();
-    let mut nat: Vec<(i128, i128)> = vec![];
-    let mut delivered_by_nat = vec![];
-    let mut q = VecDeque::new();
-    loop {
-        if q.is_empty() {
-            match try_to_get_next_input_instruction(&mut computers) {
-                Some(packets) => {
-                    for packet in packets {
-                        q.push_back(packet);
-                    }
-                }
-                None => {
-                    let (x, y) = nat.last().unwrap().clone();
-                    if !delivered_by_nat.is_empty() && delivered_by_nat.last().unwrap() == &y {
-                        return y;
-                    }
-                    delivered_by_nat.push(y);
-                    q.push_back((0, x, y));
-                }
-            }
-        } else {
-            let (dest, x, y) = q.pop_back().unwrap();
-            if dest == 255 {
-                nat.push((x, y));
-                continue;
-            }
-            computers[dest].queue_input(x);
-            computers[dest].queue_input(y);
-            let (output, _) = computers[dest].run_and_collect_all_output();
-            for packet in interpret_output(output) {
-                q.push_back(packet);
+#[test]
+fn packets_for_addresses_that_arent_there_are_dropped() {
+    // reads its address, sends packets to -5, 1000 and then the nat, and keeps asking for more
+    let proggy =
+        parse("3,100,104,-5,104,1,104,2,104,1000,104,1,104,2,104,255,104,0,104,7,3,100,1105,1,20");
+    for &until in &[Until::FirstPacketToNat, Until::NatRepeatsItself] {
+        assert_eq!(Ok(7), run_network(&proggy, until));
+        assert_eq!(Ok(7), run_threaded(&proggy, until));
+    }
+}
+
+#[test]
+fn a_computer_that_fails_stops_the_network() {
+    use crate::intcode::IntcodeErrorKind;
+
+    // reads its address and then runs into a 42, which isn't an instruction
+    let proggy = parse("3,100,42");
+    for &until in &[Until::FirstPacketToNat, Until::NatRepeatsItself] {
+        // one thread runs the computers in order, with threads it's whichever fails first
+        match run_network(&proggy, until) {
+            Err(NoAnswer::Failed(e)) => assert_eq!((0, 2), (e.node, e.error.pc)),
+            otherwise => panic!("didn't expect {:?}", otherwise),
+        }
+        match run_threaded(&proggy, until) {
+            Err(NoAnswer::Failed(e)) => {
+                assert_eq!(IntcodeErrorKind::UnknownInstruction, e.error.kind)
             }
+            otherwise => panic!("didn't expect {:?}", otherwise),
         }
     }
+
+    // and one that halts just stops taking packets
+    let proggy = parse("3,100,104,255,104,0,104,9,99");
+    for &until in &[Until::FirstPacketToNat, Until::NatRepeatsItself] {
+        assert_eq!(Ok(9), run_network(&proggy, until));
+        assert_eq!(Ok(9), run_threaded(&proggy, until));
+    }
 }
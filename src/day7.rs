@@ -1,292 +1,59 @@
-use crate::day7::Instruction::{Add1, Multiply2, Input3, Output4, Halt99, JumpIfTrue5, JumpIfFalse6, LessThan7, Equals8};
-use crate::day7::ParameterMode::{PositionMode0, ImmediateMode1};
+use crate::intcode::network::{Action, Monitor, Network, Node, Route};
+use crate::intcode::IntCodeComputer;
 use itertools::Itertools;
-use std::collections::VecDeque;
 
-#[derive(Debug)]
-enum Instruction {
-    Add1(ParameterMode, ParameterMode, ParameterMode),
-    Multiply2(ParameterMode, ParameterMode, ParameterMode),
-    Input3,
-    Output4(ParameterMode),
-    JumpIfTrue5(ParameterMode, ParameterMode),
-    JumpIfFalse6(ParameterMode, ParameterMode),
-    LessThan7(ParameterMode, ParameterMode, ParameterMode),
-    Equals8(ParameterMode, ParameterMode, ParameterMode),
-    Halt99,
+// where amplifier E's output goes. in feedback loop mode it goes back around to A until they've
+// all halted
+struct Thrusters {
+    feedback: bool,
+    output_signal: Option<i128>,
 }
 
-impl Instruction {
-    fn parse(s: &str) -> Self {
-        let parsed = if s.ends_with("1") {
-            let s = format!("{:0>5}", s);
-            let third_param_mode = ParameterMode::parse(s.chars().nth(0).unwrap());
-            let second_param_mode = ParameterMode::parse(s.chars().nth(1).unwrap());
-            let first_param_mode = ParameterMode::parse(s.chars().nth(2).unwrap());
-            Add1(first_param_mode, second_param_mode, third_param_mode)
-        } else if s.ends_with("2") {
-            let s = format!("{:0>5}", s);
-            let third_param_mode = ParameterMode::parse(s.chars().nth(0).unwrap());
-            let second_param_mode = ParameterMode::parse(s.chars().nth(1).unwrap());
-            let first_param_mode = ParameterMode::parse(s.chars().nth(2).unwrap());
-            Multiply2(first_param_mode, second_param_mode, third_param_mode)
-        } else if s.ends_with("3") {
-            Input3
-        } else if s.ends_with("4") {
-            let s = format!("{:0>3}", s);
-            let first_param_mode = ParameterMode::parse(s.chars().nth(0).unwrap());
-            Output4(first_param_mode)
-        } else if s.ends_with("5") {
-            let s = format!("{:0>4}", s);
-            let second_param_mode = ParameterMode::parse(s.chars().nth(0).unwrap());
-            let first_param_mode = ParameterMode::parse(s.chars().nth(1).unwrap());
-            JumpIfTrue5(first_param_mode, second_param_mode)
-        } else if s.ends_with("6") {
-            let s = format!("{:0>4}", s);
-            let second_param_mode = ParameterMode::parse(s.chars().nth(0).unwrap());
-            let first_param_mode = ParameterMode::parse(s.chars().nth(1).unwrap());
-            JumpIfFalse6(first_param_mode, second_param_mode)
-        } else if s.ends_with("7") {
-            let s = format!("{:0>5}", s);
-            let third_param_mode = ParameterMode::parse(s.chars().nth(0).unwrap());
-            let second_param_mode = ParameterMode::parse(s.chars().nth(1).unwrap());
-            let first_param_mode = ParameterMode::parse(s.chars().nth(2).unwrap());
-            LessThan7(first_param_mode, second_param_mode, third_param_mode)
-        } else if s.ends_with("8") {
-            let s = format!("{:0>5}", s);
-            let third_param_mode = ParameterMode::parse(s.chars().nth(0).unwrap());
-            let second_param_mode = ParameterMode::parse(s.chars().nth(1).unwrap());
-            let first_param_mode = ParameterMode::parse(s.chars().nth(2).unwrap());
-            Equals8(first_param_mode, second_param_mode, third_param_mode)
-        } else if s == "99" {
-            Halt99
+impl Monitor for Thrusters {
+    fn receive(&mut self, message: &[i128]) -> Action {
+        self.output_signal = Some(message[0]);
+        if self.feedback {
+            Action::Send(0, message.to_vec())
         } else {
-            panic!(format!("unable to parse instruction {}", s))
-        };
-        parsed
-    }
-}
-
-#[derive(Debug)]
-enum ParameterMode {
-    PositionMode0,
-    ImmediateMode1,
-}
-
-impl ParameterMode {
-    fn parse(c: char) -> Self {
-        match c {
-            '0' => PositionMode0,
-            '1' => ImmediateMode1,
-            _ => panic!(format!("unable to parse param mode {:?}", c))
+            Action::Stop(message[0])
         }
     }
-}
 
-fn get_first_param(proggy: &[String], instruction_pos: usize, mode: ParameterMode) -> isize {
-    let i = proggy[instruction_pos + 1].parse().unwrap();
-    match mode {
-        PositionMode0 => {
-            proggy[i as usize].parse().unwrap()
-        },
-        ImmediateMode1 => {
-            i
-        },
+    fn idle(&mut self) -> Action {
+        Action::Stop(self.output_signal.unwrap())
     }
 }
 
-fn get_second_param(proggy: &[String], instruction_pos: usize, mode: ParameterMode) -> isize {
-    let i = proggy[instruction_pos + 2].parse().unwrap();
-    match mode {
-        PositionMode0 => {
-            proggy[i as usize].parse().unwrap()
-        },
-        ImmediateMode1 => {
-            i
-        },
-    }
-}
-
-fn get_third_param(proggy: &[String], instruction_pos: usize, mode: ParameterMode) -> isize {
-    if let ParameterMode::ImmediateMode1 = mode {
-        panic!("invalid program, third param can't be immediate mode")
-    }
-    proggy[instruction_pos + 3].parse().unwrap()
+// amplifiers A through E, each one's output is the next one's input. the phase setting goes in
+// ahead of everything else, and the initial input signal is 0
+fn run_amplifiers(proggy: &[String], phase_settings: &[i128], feedback: bool) -> i128 {
+    let network = phase_settings.iter().enumerate().fold(Network::new(), |network, (i, phase_setting)| {
+        let input = if i == 0 { vec![*phase_setting, 0] } else { vec![*phase_setting] };
+        network.node(Node::new(IntCodeComputer::new(proggy.to_vec()))
+            .input(&input)
+            .route(Route::Pipe(i + 1)))
+    });
+    network
+        .monitor(phase_settings.len(), Thrusters { feedback, output_signal: None })
+        .run()
+        .unwrap()
+        .unwrap()
 }
 
 #[aoc(day7, part1)]
-pub fn solve_part1(input: &str) -> isize {
+pub fn solve_part1(input: &str) -> i128 {
     let proggy : Vec<_> = input.split(",").map(|s| s.to_owned()).collect();
-
-    // initial input signal is 0
-    (0..5).permutations(5).map(|phase_settings| {
-        let mut output_signal = 0;
-        for phase_setting in &phase_settings {
-            output_signal = run_amplifier(proggy.clone(), *phase_setting, output_signal);
-        }
-        output_signal
-    }).max().unwrap()
-}
-
-struct IntCodeComputer {
-    proggy: Vec<String>,
-    input: VecDeque<isize>,
-    current_pos: usize,
-}
-
-#[derive(Debug)]
-enum RunResult {
-    NeedMoreInput,
-    Output(isize),
-    Halt,
-}
-
-impl IntCodeComputer {
-    fn new(proggy: Vec<String>) -> Self {
-        IntCodeComputer { proggy, input: VecDeque::new(), current_pos: 0 }
-    }
-
-    fn queue_input(&mut self, input: isize) {
-        self.input.push_front(input);
-    }
-
-    fn run_and_get_next(&mut self) -> RunResult {
-        self.run().next().unwrap()
-    }
-
-    fn run(&mut self) -> impl Iterator<Item = RunResult> + '_ {
-        std::iter::from_fn(move || {
-            loop {
-                let instruction = Instruction::parse(&self.proggy[self.current_pos].to_string());
-                match instruction {
-                    Add1(first_mode, second_mode, third_mode) => {
-                        let param_1 = get_first_param(&self.proggy, self.current_pos, first_mode);
-                        let param_2 = get_second_param(&self.proggy, self.current_pos, second_mode);
-                        let param_3 = get_third_param(&self.proggy, self.current_pos, third_mode);
-                        self.proggy[param_3 as usize] = (param_1 + param_2).to_string();
-                        self.current_pos += 4;
-                    },
-                    Multiply2(first_mode, second_mode, third_mode) => {
-                        let param_1 = get_first_param(&self.proggy, self.current_pos, first_mode);
-                        let param_2 = get_second_param(&self.proggy, self.current_pos, second_mode);
-                        let param_3 = get_third_param(&self.proggy, self.current_pos, third_mode);
-                        self.proggy[param_3 as usize] = (param_1 * param_2).to_string();
-                        self.current_pos += 4;
-                    },
-                    Input3 => {
-                        let position = get_first_param(&self.proggy, self.current_pos, ImmediateMode1) as usize;
-                        match self.input.pop_back() {
-                            Some(input) => {
-                                self.proggy[position] = input.to_string();
-                                self.current_pos += 2;
-                            }
-                            None => return Some(RunResult::NeedMoreInput)
-                        }
-                    },
-                    Output4(mode) => {
-                        let param = get_first_param(&self.proggy, self.current_pos, mode);
-                        self.current_pos += 2;
-                        return Some(RunResult::Output(param))
-                    },
-                    Halt99 => {
-                        return Some(RunResult::Halt);
-                    },
-                    JumpIfTrue5(first_mode, second_mode) => {
-                        let param_1 = get_first_param(&self.proggy, self.current_pos, first_mode);
-                        let param_2 = get_second_param(&self.proggy, self.current_pos, second_mode);
-                        if param_1 != 0 {
-                            self.current_pos = param_2 as usize;
-                        } else {
-                            self.current_pos += 3;
-                        }
-                    }
-                    JumpIfFalse6(first_mode, second_mode) => {
-                        let param_1 = get_first_param(&self.proggy, self.current_pos, first_mode);
-                        let param_2 = get_second_param(&self.proggy, self.current_pos, second_mode);
-                        if param_1 == 0 {
-                            self.current_pos = param_2 as usize;
-                        } else {
-                            self.current_pos += 3;
-                        }
-                    }
-                    LessThan7(first_mode, second_mode, third_mode) => {
-                        let param_1 = get_first_param(&self.proggy, self.current_pos, first_mode);
-                        let param_2 = get_second_param(&self.proggy, self.current_pos, second_mode);
-                        let param_3 = get_third_param(&self.proggy, self.current_pos, third_mode);
-                        self.proggy[param_3 as usize] = if param_1 < param_2 {
-                            "1".to_owned()
-                        } else {
-                            "0".to_owned()
-                        };
-                        self.current_pos += 4;
-                    }
-                    Equals8(first_mode, second_mode, third_mode) => {
-                        let param_1 = get_first_param(&self.proggy, self.current_pos, first_mode);
-                        let param_2 = get_second_param(&self.proggy, self.current_pos, second_mode);
-                        let param_3 = get_third_param(&self.proggy, self.current_pos, third_mode);
-                        self.proggy[param_3 as usize] = if param_1 == param_2 {
-                            "1".to_owned()
-                        } else {
-                            "0".to_owned()
-                        };
-                        self.current_pos += 4;
-                    }
-                }
-
-            }
-        })
-    }
+    (0..5).permutations(5)
+        .map(|phase_settings| run_amplifiers(&proggy, &phase_settings, false))
+        .max().unwrap()
 }
 
 #[aoc(day7, part2)]
-pub fn solve_part2(input: &str) -> isize {
+pub fn solve_part2(input: &str) -> i128 {
     let proggy : Vec<_> = input.split(",").map(|s| s.to_owned()).collect();
-
-    (5..=9).permutations(5).map(|phase_settings| {
-        // amplifiers A through E
-        let mut amplifiers = (0..5)
-            .map(|_| IntCodeComputer::new(proggy.clone()))
-            .collect::<Vec<_>>();
-
-        // for feedback loop mode, set the phase setting as the first input instruction
-        for (phase_setting, amplifier) in phase_settings.iter().zip(amplifiers.iter_mut()) {
-            amplifier.queue_input(*phase_setting);
-        }
-
-        let mut output_signal = 0;
-        let mut amplifiers_alive = amplifiers.len();
-        while amplifiers_alive > 0 {
-            for amp in amplifiers.iter_mut() {
-                match amp.run_and_get_next() {
-                    RunResult::NeedMoreInput => {
-                        amp.queue_input(output_signal);
-                        match amp.run_and_get_next() {
-                            RunResult::Output(output) => output_signal = output,
-                            _ => panic!("expected output"),
-                        }
-                    }
-                    RunResult::Halt => {
-                        amplifiers_alive -= 1;
-                    }
-                    _ => panic!("didn't expect this to happen")
-                }
-            }
-        }
-        output_signal
-    }).max().unwrap()
-}
-
-// phase setting can be 0-4
-// returns output signal
-fn run_amplifier(proggy: Vec<String>, phase_setting: u8, input_signal: isize) -> isize {
-    let mut comp = IntCodeComputer::new(proggy);
-    comp.queue_input(phase_setting as isize);
-    comp.queue_input(input_signal);
-    let mut output = comp.run();
-    match output.next() {
-        Some(RunResult::Output(output)) => output,
-        otherwise => panic!(format!("expected output, but got {:?}", otherwise))
-    }
+    (5..=9).permutations(5)
+        .map(|phase_settings| run_amplifiers(&proggy, &phase_settings, true))
+        .max().unwrap()
 }
 
 
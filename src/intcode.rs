@@ -0,0 +1,599 @@
+pub mod arithmetic;
+pub mod ascii;
+pub mod assemble;
+pub mod async_driver;
+pub mod batch;
+pub mod cfg;
+#[cfg(test)]
+mod conformance;
+pub mod debugger;
+pub mod decompile;
+mod decode;
+pub mod disassemble;
+mod error;
+pub mod history;
+pub mod io;
+pub mod limits;
+pub mod loops;
+mod memory;
+pub mod network;
+pub mod profile;
+pub mod snapshot;
+pub mod trace;
+pub mod transpile;
+
+use crate::intcode::arithmetic::Arithmetic;
+use crate::intcode::decode::DecodeCache;
+pub use crate::intcode::decode::Decoded;
+pub use crate::intcode::error::{IntcodeError, IntcodeErrorKind};
+use crate::intcode::limits::{Limit, Limits};
+use crate::intcode::loops::LoopDetector;
+pub use crate::intcode::memory::Memory;
+use crate::intcode::trace::{JsonLinesTracer, NoTrace, SharedTracer, Tracer};
+use crate::intcode::Instruction::{
+    Add1, Equals8, Halt99, Input3, JumpIfFalse6, JumpIfTrue5, LessThan7, Multiply2, Output4,
+    RelativeBaseOffset9,
+};
+use crate::intcode::ParameterMode::{ImmediateMode1, PositionMode0, RelativeMode2};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Instruction {
+    Add1(ParameterMode, ParameterMode, ParameterMode),
+    Multiply2(ParameterMode, ParameterMode, ParameterMode),
+    Input3(ParameterMode),
+    Output4(ParameterMode),
+    JumpIfTrue5(ParameterMode, ParameterMode),
+    JumpIfFalse6(ParameterMode, ParameterMode),
+    LessThan7(ParameterMode, ParameterMode, ParameterMode),
+    Equals8(ParameterMode, ParameterMode, ParameterMode),
+    RelativeBaseOffset9(ParameterMode),
+    Halt99,
+}
+
+impl Instruction {
+    pub fn parse(code: i128) -> Self {
+        Self::try_parse(code).unwrap_or_else(|| panic!("unable to parse instruction {}", code))
+    }
+
+    pub fn try_parse(code: i128) -> Option<Self> {
+        if !(0..100_000).contains(&code) {
+            return None;
+        }
+        // i128 division is slow, and this runs on every single step
+        let code = code as u32;
+        // modes are the digits above the two opcode digits, first param's mode first
+        let first_param_mode = ParameterMode::try_parse(code / 100 % 10)?;
+        let second_param_mode = ParameterMode::try_parse(code / 1000 % 10)?;
+        let third_param_mode = ParameterMode::try_parse(code / 10000 % 10)?;
+        Some(match code % 100 {
+            1 => Add1(first_param_mode, second_param_mode, third_param_mode),
+            2 => Multiply2(first_param_mode, second_param_mode, third_param_mode),
+            3 => Input3(first_param_mode),
+            4 => Output4(first_param_mode),
+            5 => JumpIfTrue5(first_param_mode, second_param_mode),
+            6 => JumpIfFalse6(first_param_mode, second_param_mode),
+            7 => LessThan7(first_param_mode, second_param_mode, third_param_mode),
+            8 => Equals8(first_param_mode, second_param_mode, third_param_mode),
+            9 => RelativeBaseOffset9(first_param_mode),
+            99 => Halt99,
+            _ => return None,
+        })
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            Add1(..) => "add",
+            Multiply2(..) => "mul",
+            Input3(_) => "in",
+            Output4(_) => "out",
+            JumpIfTrue5(..) => "jnz",
+            JumpIfFalse6(..) => "jz",
+            LessThan7(..) => "lt",
+            Equals8(..) => "eq",
+            RelativeBaseOffset9(_) => "arb",
+            Halt99 => "halt",
+        }
+    }
+
+    pub fn modes(&self) -> Vec<ParameterMode> {
+        match *self {
+            Add1(a, b, c) | Multiply2(a, b, c) | LessThan7(a, b, c) | Equals8(a, b, c) => {
+                vec![a, b, c]
+            }
+            JumpIfTrue5(a, b) | JumpIfFalse6(a, b) => vec![a, b],
+            Input3(a) | Output4(a) | RelativeBaseOffset9(a) => vec![a],
+            Halt99 => vec![],
+        }
+    }
+
+    // how many cells the instruction takes up, including the opcode itself
+    pub fn size(&self) -> usize {
+        match self {
+            Add1(..) | Multiply2(..) | LessThan7(..) | Equals8(..) => 4,
+            JumpIfTrue5(..) | JumpIfFalse6(..) => 3,
+            Input3(_) | Output4(_) | RelativeBaseOffset9(_) => 2,
+            Halt99 => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParameterMode {
+    PositionMode0,
+    ImmediateMode1,
+    RelativeMode2,
+}
+
+impl ParameterMode {
+    pub fn try_parse(digit: u32) -> Option<Self> {
+        match digit {
+            0 => Some(PositionMode0),
+            1 => Some(ImmediateMode1),
+            2 => Some(RelativeMode2),
+            _ => None,
+        }
+    }
+}
+
+pub fn parse_proggy(input: &str) -> Vec<i128> {
+    input
+        .trim()
+        .split(",")
+        .map(|s| s.trim().parse().unwrap())
+        .collect()
+}
+
+#[derive(Clone)]
+pub struct IntCodeComputer {
+    pub num_instructions_processed: usize,
+    memory: Memory,
+    input: VecDeque<i128>,
+    current_pos: usize,
+    relative_base: i128,
+    decode_cache: DecodeCache,
+    tracer: Option<SharedTracer>,
+    limits: Limits,
+    loop_detector: Option<LoopDetector>,
+    arithmetic: Arithmetic,
+}
+
+#[derive(Debug)]
+pub enum RunResult {
+    NeedMoreInput,
+    Output(i128),
+    Halt,
+    // the machine stays stopped on the bad instruction, running it again gives the same error
+    Error(IntcodeError),
+}
+
+impl IntCodeComputer {
+    pub fn new(proggy: Vec<String>) -> Self {
+        Self::from_ints(proggy.iter().map(|s| s.trim().parse().unwrap()).collect())
+    }
+
+    pub fn from_ints(proggy: Vec<i128>) -> Self {
+        let memory = Memory::new(proggy);
+        IntCodeComputer {
+            decode_cache: DecodeCache::new(&memory),
+            memory,
+            input: VecDeque::new(),
+            current_pos: 0,
+            relative_base: 0,
+            num_instructions_processed: 0,
+            tracer: None,
+            limits: Limits::none(),
+            loop_detector: None,
+            arithmetic: Arithmetic::default(),
+        }
+    }
+
+    pub fn queue_input(&mut self, input: i128) {
+        self.input.push_front(input);
+    }
+
+    pub fn read_memory(&self, addr: usize) -> i128 {
+        self.memory[addr]
+    }
+
+    // for patching a program before it runs, like day2's noun and verb
+    pub fn write_memory(&mut self, addr: usize, value: i128) {
+        self.write(addr, value, &mut NoTrace);
+    }
+
+    pub fn run_until_halt(&mut self) -> Vec<i128> {
+        self.try_run_until_halt()
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_run_until_halt(&mut self) -> Result<Vec<i128>, IntcodeError> {
+        let mut all_output = vec![];
+        loop {
+            match self.run_and_get_next() {
+                RunResult::Output(output) => all_output.push(output),
+                RunResult::Halt => break,
+                RunResult::NeedMoreInput => {
+                    return Err(self.error(IntcodeErrorKind::RanOutOfInput))
+                }
+                RunResult::Error(e) => return Err(e),
+            }
+        }
+        Ok(all_output)
+    }
+
+    pub fn run_and_collect_all_output(&mut self) -> (Vec<i128>, RunResult) {
+        let mut all_output = vec![];
+        let mut result;
+        loop {
+            result = self.run_and_get_next();
+            match result {
+                RunResult::Output(output) => all_output.push(output),
+                RunResult::NeedMoreInput | RunResult::Halt | RunResult::Error(_) => break,
+            }
+        }
+        (all_output, result)
+    }
+
+    pub fn run_and_get_next(&mut self) -> RunResult {
+        self.run().next().unwrap()
+    }
+
+    pub fn disable_decode_cache(&mut self) {
+        self.decode_cache = DecodeCache::default();
+    }
+
+    // every instruction the machine runs from now on gets reported to the tracer
+    pub fn trace_to(&mut self, tracer: SharedTracer) {
+        self.tracer = Some(tracer);
+    }
+
+    // traces to a file with one json object per instruction, see JsonLinesTracer
+    pub fn trace_json_lines(&mut self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        self.trace_to(Arc::new(Mutex::new(JsonLinesTracer::create(path)?)));
+        Ok(())
+    }
+
+    // the file gets flushed once the last clone tracing into it stops
+    pub fn stop_tracing(&mut self) {
+        self.tracer = None;
+    }
+
+    fn error(&self, kind: IntcodeErrorKind) -> IntcodeError {
+        IntcodeError {
+            kind,
+            pc: self.current_pos,
+            raw_instruction: self.memory[self.current_pos],
+            relative_base: self.relative_base,
+        }
+    }
+
+    fn addr(raw: i128) -> Result<usize, IntcodeErrorKind> {
+        use std::convert::TryFrom;
+        usize::try_from(raw).map_err(|_| IntcodeErrorKind::InvalidAddress(raw))
+    }
+
+    // addr, for the running program, which isn't allowed past the address limit
+    fn limited_addr(&self, raw: i128) -> Result<usize, IntcodeErrorKind> {
+        let addr = Self::addr(raw)?;
+        if addr > self.limits.address {
+            return Err(IntcodeErrorKind::LimitExceeded(Limit::Address(self.limits.address)));
+        }
+        Ok(addr)
+    }
+
+    // a relative mode operand's address. one so far out that it can't be added to the relative base
+    // is saturated, which is never a valid address either
+    fn relative_addr(&self, operand: i128) -> Result<usize, IntcodeErrorKind> {
+        self.limited_addr(operand.saturating_add(self.relative_base))
+    }
+
+    #[inline(always)]
+    fn read_param<T: Tracer + ?Sized>(
+        &self,
+        operand: i128,
+        mode: ParameterMode,
+        tracer: &mut T,
+    ) -> Result<i128, IntcodeErrorKind> {
+        let value = match mode {
+            PositionMode0 => self.memory[self.limited_addr(operand)?],
+            ImmediateMode1 => operand,
+            RelativeMode2 => self.memory[self.relative_addr(operand)?],
+        };
+        tracer.read(value);
+        Ok(value)
+    }
+
+    // the address an instruction writes its result to
+    fn write_param(&self, operand: i128, mode: ParameterMode) -> Result<usize, IntcodeErrorKind> {
+        let addr = match mode {
+            PositionMode0 => self.limited_addr(operand)?,
+            ImmediateMode1 => return Err(IntcodeErrorKind::ImmediateModeWrite),
+            RelativeMode2 => self.relative_addr(operand)?,
+        };
+        // only writes past the end can make memory any bigger
+        if addr >= self.memory.dense_len()
+            && self.memory.cells_after_write(addr) > self.limits.stored_cells
+        {
+            return Err(IntcodeErrorKind::LimitExceeded(Limit::StoredCells(self.limits.stored_cells)));
+        }
+        Ok(addr)
+    }
+
+    // the address the instruction at pc is going to write to, if it writes anywhere
+    fn next_write(&mut self) -> Option<usize> {
+        let decoded = self.decode_cache.get(&self.memory, self.current_pos)?;
+        let (mode, operand) = match decoded.instruction {
+            Input3(mode) => (mode, decoded.operands[0]),
+            Add1(_, _, mode)
+            | Multiply2(_, _, mode)
+            | LessThan7(_, _, mode)
+            | Equals8(_, _, mode) => (mode, decoded.operands[2]),
+            _ => return None,
+        };
+        self.write_param(operand, mode).ok()
+    }
+
+    #[inline(always)]
+    fn write<T: Tracer + ?Sized>(&mut self, addr: usize, value: i128, tracer: &mut T) {
+        tracer.write(addr, value);
+        if let Some(detector) = self.loop_detector.as_mut() {
+            detector.write(addr, self.memory[addr]);
+        }
+        self.memory[addr] = value;
+        self.decode_cache.invalidate(addr);
+    }
+
+    pub fn run(&mut self) -> impl Iterator<Item = RunResult> + '_ {
+        // checked once per result rather than once per instruction, the untraced loop is the
+        // same as if tracing didn't exist
+        std::iter::from_fn(move || match self.tracer.clone() {
+            None => loop {
+                if let Some(result) = self.run_one(&mut NoTrace) {
+                    return Some(result);
+                }
+            },
+            Some(tracer) => {
+                let mut tracer = tracer.lock().unwrap();
+                loop {
+                    if let Some(result) = self.run_one(&mut *tracer) {
+                        return Some(result);
+                    }
+                }
+            }
+        })
+    }
+
+    // runs a single instruction, and traces it if tracing is on
+    fn run_one_traced(&mut self) -> Option<RunResult> {
+        match self.tracer.clone() {
+            None => self.run_one(&mut NoTrace),
+            Some(tracer) => self.run_one(&mut *tracer.lock().unwrap()),
+        }
+    }
+
+    // step plus the bookkeeping around it, None if the instruction didn't produce anything.
+    // this, step, read_param and write are forced inline: left to itself the compiler stopped
+    // inlining them once they got generic over the tracer, and the NoTrace loop got a lot slower
+    #[inline(always)]
+    fn run_one<T: Tracer + ?Sized>(&mut self, tracer: &mut T) -> Option<RunResult> {
+        if let Some(mut detector) = self.loop_detector.take() {
+            let stuck = detector.check(self);
+            self.loop_detector = Some(detector);
+            if let Some(kind) = stuck {
+                return Some(RunResult::Error(self.error(kind)));
+            }
+        }
+        match self.step(tracer) {
+            Ok(result) => {
+                tracer.end(None);
+                // an input with nothing to read hasn't run, it runs again once there's input
+                if !matches!(result, Some(RunResult::NeedMoreInput)) {
+                    self.num_instructions_processed += 1;
+                }
+                if let Some(detector) = self.loop_detector.as_mut() {
+                    match result {
+                        None | Some(RunResult::Output(_)) => detector.stepped(),
+                        Some(_) => detector.input(),
+                    }
+                }
+                result
+            }
+            Err(kind) => {
+                let error = self.error(kind);
+                tracer.end(Some(&error));
+                Some(RunResult::Error(error))
+            }
+        }
+    }
+
+    // runs one instruction, and returns what it produced if it has to stop for it. nothing in the
+    // machine changes when this returns an error
+    #[inline(always)]
+    fn step<T: Tracer + ?Sized>(
+        &mut self,
+        tracer: &mut T,
+    ) -> Result<Option<RunResult>, IntcodeErrorKind> {
+        let decoded = self.decode_cache.get(&self.memory, self.current_pos);
+        tracer.begin(
+            self.num_instructions_processed,
+            self.current_pos,
+            decoded.map(|decoded| decoded.instruction),
+        );
+        if self.num_instructions_processed >= self.limits.steps {
+            return Err(IntcodeErrorKind::LimitExceeded(Limit::Steps(self.limits.steps)));
+        }
+        if self.current_pos > self.limits.address {
+            return Err(IntcodeErrorKind::LimitExceeded(Limit::Address(self.limits.address)));
+        }
+        // moving past an instruction this close to the top of memory would wrap around to 0
+        if self.current_pos > usize::MAX - 4 {
+            return Err(IntcodeErrorKind::InvalidAddress(self.current_pos as i128));
+        }
+        let Decoded {
+            instruction,
+            operands: [op_1, op_2, op_3],
+        } = decoded.ok_or(IntcodeErrorKind::UnknownInstruction)?;
+        match instruction {
+            Add1(first_mode, second_mode, third_mode) => {
+                let param_1 = self.read_param(op_1, first_mode, tracer)?;
+                let param_2 = self.read_param(op_2, second_mode, tracer)?;
+                let param_3 = self.write_param(op_3, third_mode)?;
+                let value = self
+                    .arithmetic
+                    .add(param_1, param_2)
+                    .ok_or(IntcodeErrorKind::Overflow)?;
+                self.write(param_3, value, tracer);
+                self.current_pos += 4;
+            }
+            Multiply2(first_mode, second_mode, third_mode) => {
+                let param_1 = self.read_param(op_1, first_mode, tracer)?;
+                let param_2 = self.read_param(op_2, second_mode, tracer)?;
+                let param_3 = self.write_param(op_3, third_mode)?;
+                let value = self
+                    .arithmetic
+                    .mul(param_1, param_2)
+                    .ok_or(IntcodeErrorKind::Overflow)?;
+                self.write(param_3, value, tracer);
+                self.current_pos += 4;
+            }
+            Input3(mode) => {
+                let raw_position = self.write_param(op_1, mode)?;
+                match self.input.pop_back() {
+                    Some(input) => {
+                        tracer.input(input);
+                        if let Some(detector) = self.loop_detector.as_mut() {
+                            detector.input();
+                        }
+                        self.write(raw_position, input, tracer);
+                        self.current_pos += 2;
+                    }
+                    None => {
+                        tracer.waiting_for_input();
+                        return Ok(Some(RunResult::NeedMoreInput));
+                    }
+                }
+            }
+            Output4(mode) => {
+                let param = self.read_param(op_1, mode, tracer)?;
+                tracer.output(param);
+                self.current_pos += 2;
+                return Ok(Some(RunResult::Output(param)));
+            }
+            Halt99 => {
+                return Ok(Some(RunResult::Halt));
+            }
+            JumpIfTrue5(first_mode, second_mode) => {
+                let param_1 = self.read_param(op_1, first_mode, tracer)?;
+                let param_2 = self.read_param(op_2, second_mode, tracer)?;
+                if param_1 != 0 {
+                    self.current_pos = self.limited_addr(param_2)?;
+                } else {
+                    self.current_pos += 3;
+                }
+            }
+            JumpIfFalse6(first_mode, second_mode) => {
+                let param_1 = self.read_param(op_1, first_mode, tracer)?;
+                let param_2 = self.read_param(op_2, second_mode, tracer)?;
+                if param_1 == 0 {
+                    self.current_pos = self.limited_addr(param_2)?;
+                } else {
+                    self.current_pos += 3;
+                }
+            }
+            LessThan7(first_mode, second_mode, third_mode) => {
+                let param_1 = self.read_param(op_1, first_mode, tracer)?;
+                let param_2 = self.read_param(op_2, second_mode, tracer)?;
+                let param_3 = self.write_param(op_3, third_mode)?;
+                self.write(param_3, (param_1 < param_2) as i128, tracer);
+                self.current_pos += 4;
+            }
+            Equals8(first_mode, second_mode, third_mode) => {
+                let param_1 = self.read_param(op_1, first_mode, tracer)?;
+                let param_2 = self.read_param(op_2, second_mode, tracer)?;
+                let param_3 = self.write_param(op_3, third_mode)?;
+                self.write(param_3, (param_1 == param_2) as i128, tracer);
+                self.current_pos += 4;
+            }
+            RelativeBaseOffset9(first_mode) => {
+                let param_1 = self.read_param(op_1, first_mode, tracer)?;
+                let relative_base = self
+                    .arithmetic
+                    .add(self.relative_base, param_1)
+                    .ok_or(IntcodeErrorKind::Overflow)?;
+                tracer.relative_base(self.relative_base, relative_base);
+                self.relative_base = relative_base;
+                self.current_pos += 2;
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[test]
+fn errors_instead_of_panicking() {
+    let error = |kind, pc, raw_instruction, relative_base| IntcodeError {
+        kind,
+        pc,
+        raw_instruction,
+        relative_base,
+    };
+
+    let mut icc = IntCodeComputer::from_ints(vec![104, 1, 42]);
+    assert_eq!(
+        Err(error(IntcodeErrorKind::UnknownInstruction, 2, 42, 0)),
+        icc.try_run_until_halt()
+    );
+    // stays stuck on the bad instruction
+    assert_eq!(
+        Err(error(IntcodeErrorKind::UnknownInstruction, 2, 42, 0)),
+        icc.try_run_until_halt()
+    );
+
+    let mut icc = IntCodeComputer::from_ints(vec![109, 5, 11101, 1, 1, 3, 99]);
+    assert_eq!(
+        Err(error(IntcodeErrorKind::ImmediateModeWrite, 2, 11101, 5)),
+        icc.try_run_until_halt()
+    );
+
+    let mut icc = IntCodeComputer::from_ints(vec![109, -10, 204, 3, 99]);
+    assert_eq!(
+        Err(error(IntcodeErrorKind::InvalidAddress(-7), 2, 204, -10)),
+        icc.try_run_until_halt()
+    );
+
+    let mut icc = IntCodeComputer::from_ints(vec![1105, 1, -1]);
+    assert_eq!(
+        Err(error(IntcodeErrorKind::InvalidAddress(-1), 0, 1105, 0)),
+        icc.try_run_until_halt()
+    );
+
+    let mut icc = IntCodeComputer::from_ints(vec![3, 3, 99, 0]);
+    assert_eq!(
+        Err(error(IntcodeErrorKind::RanOutOfInput, 0, 3, 0)),
+        icc.try_run_until_halt()
+    );
+    icc.queue_input(7);
+    assert_eq!(Ok(vec![]), icc.try_run_until_halt());
+
+    // numbers too big to add to the relative base
+    let mut icc = IntCodeComputer::from_ints(vec![109, i128::MAX, 204, 1, 99]);
+    assert_eq!(
+        Err(error(IntcodeErrorKind::InvalidAddress(i128::MAX), 2, 204, i128::MAX)),
+        icc.try_run_until_halt()
+    );
+    let mut icc = IntCodeComputer::from_ints(vec![109, i128::MAX, 109, 1, 99]);
+    assert_eq!(
+        Err(error(IntcodeErrorKind::Overflow, 2, 109, i128::MAX)),
+        icc.try_run_until_halt()
+    );
+
+    // jumping to the very top of memory
+    let top = usize::MAX as i128;
+    let mut icc = IntCodeComputer::from_ints(vec![1105, 1, top]);
+    assert_eq!(
+        Err(error(IntcodeErrorKind::InvalidAddress(top), usize::MAX, 0, 0)),
+        icc.try_run_until_halt()
+    );
+}
@@ -0,0 +1,320 @@
+use crate::intcode::Instruction::{
+    Add1, Equals8, Halt99, Input3, JumpIfFalse6, JumpIfTrue5, LessThan7, Multiply2, Output4,
+    RelativeBaseOffset9,
+};
+use crate::intcode::ParameterMode::{ImmediateMode1, PositionMode0, RelativeMode2};
+use crate::intcode::{Instruction, IntCodeComputer, IntcodeError, IntcodeErrorKind, ParameterMode};
+use num::{BigInt, Signed, ToPrimitive, Zero};
+use std::collections::{HashMap, VecDeque};
+
+// what add and mul do when the answer doesn't fit in an i128 cell
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Arithmetic {
+    // the instruction fails with an Overflow error instead. like any other error the machine stays
+    // on it, so it can be handed over to a BigIntCodeComputer to carry on with
+    #[default]
+    Checked,
+    // wraps around, same as plain i128 math in a release build
+    Wrapping,
+}
+
+impl Arithmetic {
+    pub fn add(self, a: i128, b: i128) -> Option<i128> {
+        match self {
+            Arithmetic::Checked => a.checked_add(b),
+            Arithmetic::Wrapping => Some(a.wrapping_add(b)),
+        }
+    }
+
+    pub fn mul(self, a: i128, b: i128) -> Option<i128> {
+        match self {
+            Arithmetic::Checked => a.checked_mul(b),
+            Arithmetic::Wrapping => Some(a.wrapping_mul(b)),
+        }
+    }
+}
+
+impl IntCodeComputer {
+    pub fn set_arithmetic(&mut self, arithmetic: Arithmetic) {
+        self.arithmetic = arithmetic;
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BigRunResult {
+    NeedMoreInput,
+    Output(BigInt),
+    Halt,
+    Error(IntcodeError),
+}
+
+// a machine whose cells can hold numbers of any size, for programs that don't fit in i128. it's a
+// lot slower than IntCodeComputer and doesn't do any of its extras (tracing, limits and so on)
+#[derive(Clone, Debug, Default)]
+pub struct BigIntCodeComputer {
+    pub num_instructions_processed: usize,
+    // the program image, and anything written too far past it to grow it over
+    dense: Vec<BigInt>,
+    sparse: HashMap<usize, BigInt>,
+    input: VecDeque<BigInt>,
+    current_pos: usize,
+    relative_base: BigInt,
+}
+
+// for the i128 fields of errors, numbers too big for them come out as the biggest there is
+fn saturate(value: &BigInt) -> i128 {
+    value.to_i128().unwrap_or(if value.is_positive() {
+        i128::MAX
+    } else {
+        i128::MIN
+    })
+}
+
+impl BigIntCodeComputer {
+    pub fn from_ints(proggy: Vec<i128>) -> Self {
+        Self {
+            dense: proggy.into_iter().map(BigInt::from).collect(),
+            ..Self::default()
+        }
+    }
+
+    pub fn queue_input(&mut self, input: impl Into<BigInt>) {
+        self.input.push_front(input.into());
+    }
+
+    pub fn run_until_halt(&mut self) -> Vec<BigInt> {
+        self.try_run_until_halt()
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_run_until_halt(&mut self) -> Result<Vec<BigInt>, IntcodeError> {
+        let mut all_output = vec![];
+        loop {
+            match self.run_and_get_next() {
+                BigRunResult::Output(output) => all_output.push(output),
+                BigRunResult::Halt => return Ok(all_output),
+                BigRunResult::NeedMoreInput => {
+                    return Err(self.error(IntcodeErrorKind::RanOutOfInput))
+                }
+                BigRunResult::Error(e) => return Err(e),
+            }
+        }
+    }
+
+    pub fn run_and_get_next(&mut self) -> BigRunResult {
+        loop {
+            match self.step() {
+                Ok(None) => self.num_instructions_processed += 1,
+                Ok(Some(result)) => {
+                    self.num_instructions_processed += 1;
+                    return result;
+                }
+                Err(kind) => return BigRunResult::Error(self.error(kind)),
+            }
+        }
+    }
+
+    fn error(&self, kind: IntcodeErrorKind) -> IntcodeError {
+        IntcodeError {
+            kind,
+            pc: self.current_pos,
+            raw_instruction: saturate(&self.get(self.current_pos)),
+            relative_base: saturate(&self.relative_base),
+        }
+    }
+
+    fn get(&self, addr: usize) -> BigInt {
+        match self.dense.get(addr) {
+            Some(value) => value.clone(),
+            None => self.sparse.get(&addr).cloned().unwrap_or_default(),
+        }
+    }
+
+    fn set(&mut self, addr: usize, value: BigInt) {
+        if addr < self.dense.len() {
+            self.dense[addr] = value;
+        } else if addr - self.dense.len() < 4096 {
+            let old_len = self.dense.len();
+            self.dense.resize(addr + 1, BigInt::zero());
+            for moved in old_len..addr {
+                if let Some(value) = self.sparse.remove(&moved) {
+                    self.dense[moved] = value;
+                }
+            }
+            self.sparse.remove(&addr);
+            self.dense[addr] = value;
+        } else {
+            self.sparse.insert(addr, value);
+        }
+    }
+
+    fn addr(raw: BigInt) -> Result<usize, IntcodeErrorKind> {
+        raw.to_usize()
+            .ok_or_else(|| IntcodeErrorKind::InvalidAddress(saturate(&raw)))
+    }
+
+    // the address this far past pc. an instruction at the very top of memory would have its
+    // operands and the next instruction wrap around to 0, which the interpreter calls an invalid
+    // address too
+    fn past_pc(&self, by: usize) -> Result<usize, IntcodeErrorKind> {
+        self.current_pos
+            .checked_add(by)
+            .ok_or(IntcodeErrorKind::InvalidAddress(self.current_pos as i128))
+    }
+
+    fn read(&self, modes: &[ParameterMode], i: usize) -> Result<BigInt, IntcodeErrorKind> {
+        let operand = self.get(self.past_pc(1 + i)?);
+        Ok(match modes[i] {
+            PositionMode0 => self.get(Self::addr(operand)?),
+            ImmediateMode1 => operand,
+            RelativeMode2 => self.get(Self::addr(operand + &self.relative_base)?),
+        })
+    }
+
+    fn write_addr(&self, modes: &[ParameterMode], i: usize) -> Result<usize, IntcodeErrorKind> {
+        let operand = self.get(self.past_pc(1 + i)?);
+        match modes[i] {
+            PositionMode0 => Self::addr(operand),
+            ImmediateMode1 => Err(IntcodeErrorKind::ImmediateModeWrite),
+            RelativeMode2 => Self::addr(operand + &self.relative_base),
+        }
+    }
+
+    // the same as IntCodeComputer::step, nothing changes if it gives back an error
+    fn step(&mut self) -> Result<Option<BigRunResult>, IntcodeErrorKind> {
+        let instruction = self
+            .get(self.current_pos)
+            .to_i128()
+            .and_then(Instruction::try_parse)
+            .ok_or(IntcodeErrorKind::UnknownInstruction)?;
+        let mut next_pos = self.past_pc(instruction.size())?;
+        let modes = instruction.modes();
+        match instruction {
+            Add1(..) | Multiply2(..) | LessThan7(..) | Equals8(..) => {
+                let (a, b) = (self.read(&modes, 0)?, self.read(&modes, 1)?);
+                let dest = self.write_addr(&modes, 2)?;
+                let value = match instruction {
+                    Add1(..) => a + b,
+                    Multiply2(..) => a * b,
+                    LessThan7(..) => BigInt::from((a < b) as u8),
+                    _ => BigInt::from((a == b) as u8),
+                };
+                self.set(dest, value);
+            }
+            Input3(_) => {
+                let dest = self.write_addr(&modes, 0)?;
+                match self.input.pop_back() {
+                    Some(input) => self.set(dest, input),
+                    None => return Ok(Some(BigRunResult::NeedMoreInput)),
+                }
+            }
+            Output4(_) => {
+                let value = self.read(&modes, 0)?;
+                self.current_pos = next_pos;
+                return Ok(Some(BigRunResult::Output(value)));
+            }
+            JumpIfTrue5(..) | JumpIfFalse6(..) => {
+                let condition = self.read(&modes, 0)?;
+                let target = self.read(&modes, 1)?;
+                if condition.is_zero() == matches!(instruction, JumpIfFalse6(..)) {
+                    next_pos = Self::addr(target)?;
+                }
+            }
+            RelativeBaseOffset9(_) => self.relative_base += self.read(&modes, 0)?,
+            Halt99 => return Ok(Some(BigRunResult::Halt)),
+        }
+        self.current_pos = next_pos;
+        Ok(None)
+    }
+}
+
+// picks up right where the machine is, say after it stopped on an Overflow error
+impl From<&IntCodeComputer> for BigIntCodeComputer {
+    fn from(icc: &IntCodeComputer) -> Self {
+        Self {
+            num_instructions_processed: icc.num_instructions_processed,
+            dense: icc.memory.dense().map(BigInt::from).collect(),
+            sparse: icc
+                .memory
+                .sparse()
+                .iter()
+                .map(|(&addr, &v)| (addr, BigInt::from(v)))
+                .collect(),
+            input: icc.input.iter().map(|&v| BigInt::from(v)).collect(),
+            current_pos: icc.current_pos,
+            relative_base: BigInt::from(icc.relative_base),
+        }
+    }
+}
+
+#[test]
+fn overflow_is_an_error_or_wraps_or_keeps_going_with_big_cells() {
+    use crate::intcode::assemble::assemble;
+    use crate::intcode::RunResult;
+
+    // squares 3 over and over, printing each one, until it's done it 8 times
+    let proggy = assemble(
+        "
+        loop: mul [x], [x], [x]
+        out [x]
+        add [n], 1, [n]
+        lt [n], 8, [more]
+        jnz [more], loop
+        halt
+        x: data 3
+        n: data 0
+        more: data 0
+        ",
+    )
+    .unwrap();
+    let squares = (1..=8)
+        .map(|n| num::pow(BigInt::from(3), 1 << n))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        squares,
+        BigIntCodeComputer::from_ints(proggy.clone()).run_until_halt()
+    );
+
+    // 3^128 is the first one that doesn't fit
+    let mut icc = IntCodeComputer::from_ints(proggy.clone());
+    let (output, result) = icc.run_and_collect_all_output();
+    assert_eq!(6, output.len());
+    assert!(matches!(
+        result,
+        RunResult::Error(IntcodeError {
+            kind: IntcodeErrorKind::Overflow,
+            pc: 0,
+            ..
+        })
+    ));
+    assert_eq!(
+        squares[6..].to_vec(),
+        BigIntCodeComputer::from(&icc).run_until_halt()
+    );
+
+    let mut icc = IntCodeComputer::from_ints(proggy);
+    icc.set_arithmetic(Arithmetic::Wrapping);
+    let output = icc.run_until_halt();
+    assert_eq!(8, output.len());
+    assert_ne!(squares[7].to_i128(), Some(output[7]));
+}
+
+#[test]
+fn big_cells_stop_at_the_top_of_memory_too() {
+    let top = usize::MAX as i128;
+    // puts a halt at the very top and jumps to it, where the next instruction would wrap around
+    // to 0. the interpreter stops there the same way
+    let proggy = vec![1101, 99, 0, top, 1105, 1, top];
+    let e = BigIntCodeComputer::from_ints(proggy.clone())
+        .try_run_until_halt()
+        .unwrap_err();
+    assert_eq!(IntcodeErrorKind::InvalidAddress(top), e.kind);
+    assert_eq!(usize::MAX, e.pc);
+    let e = IntCodeComputer::from_ints(proggy)
+        .try_run_until_halt()
+        .unwrap_err();
+    assert_eq!(
+        (e.kind, e.pc),
+        (IntcodeErrorKind::InvalidAddress(top), usize::MAX)
+    );
+}
@@ -0,0 +1,196 @@
+use crate::intcode::{IntCodeComputer, IntcodeError, RunResult};
+use std::convert::TryFrom;
+use std::fmt;
+
+// talks to programs that speak text, one character per input and output. they print until they
+// want a line of input, and some of them finish by printing a number that's too big to be a
+// character, the puzzle answer
+pub struct AsciiComputer {
+    pub icc: IntCodeComputer,
+}
+
+// everything the program printed up to the next time it wanted input, or up to when it halted
+#[derive(Debug, PartialEq)]
+pub struct AsciiOutput {
+    pub text: String,
+    // the value that isn't a character, if the program printed one right before halting
+    pub answer: Option<i128>,
+    pub halted: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AsciiError {
+    // something that isn't a character came out where the program should still have been
+    // printing text
+    NotAscii { value: i128, text_so_far: String },
+    // a line that can't be sent, because the program only reads ascii
+    NonAsciiInput(String),
+    Intcode(IntcodeError),
+}
+
+impl fmt::Display for AsciiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AsciiError::NotAscii { value, text_so_far } => write!(
+                f,
+                "expected ascii text but got {} after {:?}",
+                value, text_so_far
+            ),
+            AsciiError::NonAsciiInput(line) => write!(f, "can't send {:?}, it isn't ascii", line),
+            AsciiError::Intcode(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for AsciiError {}
+
+fn to_char(value: i128) -> Option<char> {
+    u8::try_from(value)
+        .ok()
+        .filter(|byte| byte.is_ascii())
+        .map(char::from)
+}
+
+impl AsciiComputer {
+    pub fn new(icc: IntCodeComputer) -> Self {
+        Self { icc }
+    }
+
+    // queues up a line of input, the newline gets added if it isn't there already. nothing gets
+    // queued if any of it isn't ascii
+    pub fn send_line(&mut self, line: &str) -> Result<(), AsciiError> {
+        if !line.is_ascii() {
+            return Err(AsciiError::NonAsciiInput(line.to_owned()));
+        }
+        for chr in line.chars() {
+            self.icc.queue_input(chr as i128);
+        }
+        if !line.ends_with('\n') {
+            self.icc.queue_input('\n' as i128);
+        }
+        Ok(())
+    }
+
+    // runs until the program wants more input than what's queued, or until it halts
+    pub fn read(&mut self) -> Result<AsciiOutput, AsciiError> {
+        let mut text = String::new();
+        let mut answer = None;
+        loop {
+            match self.icc.run_and_get_next() {
+                RunResult::Output(value) => {
+                    // the answer has to be the last thing printed, otherwise it's garbage
+                    if let Some(value) = answer.take() {
+                        return Err(AsciiError::NotAscii {
+                            value,
+                            text_so_far: text,
+                        });
+                    }
+                    match to_char(value) {
+                        Some(chr) => text.push(chr),
+                        None => answer = Some(value),
+                    }
+                }
+                RunResult::NeedMoreInput => {
+                    return match answer {
+                        Some(value) => Err(AsciiError::NotAscii {
+                            value,
+                            text_so_far: text,
+                        }),
+                        None => Ok(AsciiOutput {
+                            text,
+                            answer,
+                            halted: false,
+                        }),
+                    }
+                }
+                RunResult::Halt => {
+                    return Ok(AsciiOutput {
+                        text,
+                        answer,
+                        halted: true,
+                    })
+                }
+                RunResult::Error(e) => return Err(AsciiError::Intcode(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+fn echo(answer: i128) -> AsciiComputer {
+    use crate::intcode::assemble::assemble;
+
+    // prints a prompt, then echoes back a line before printing the answer
+    let proggy = assemble(&format!(
+        "
+        out 62
+        out 10
+        loop: in [chr]
+        out [chr]
+        eq [chr], 10, [done]
+        jz [done], loop
+        out {}
+        halt
+        chr: data 0
+        done: data 0
+        ",
+        answer
+    ))
+    .unwrap();
+    AsciiComputer::new(IntCodeComputer::from_ints(proggy))
+}
+
+#[test]
+fn reads_text_up_to_prompts_and_the_answer() {
+    let mut ascii = echo(1000);
+    assert_eq!(
+        Ok(AsciiOutput {
+            text: ">\n".to_owned(),
+            answer: None,
+            halted: false
+        }),
+        ascii.read()
+    );
+    ascii.send_line("hello").unwrap();
+    assert_eq!(
+        Ok(AsciiOutput {
+            text: "hello\n".to_owned(),
+            answer: Some(1000),
+            halted: true
+        }),
+        ascii.read()
+    );
+
+    // an answer that happens to look like a character is just text
+    let mut ascii = echo(33);
+    ascii.send_line("hi\n").unwrap();
+    let output = ascii.read().unwrap();
+    assert_eq!((">\nhi\n!", None), (&output.text[..], output.answer));
+}
+
+#[test]
+fn non_ascii_in_the_middle_of_text_is_an_error() {
+    let mut ascii = echo(1000);
+    ascii.icc.queue_input(300);
+    assert_eq!(
+        Err(AsciiError::NotAscii {
+            value: 300,
+            text_so_far: ">\n".to_owned()
+        }),
+        ascii.read()
+    );
+}
+
+#[test]
+fn non_ascii_lines_arent_sent() {
+    let mut ascii = echo(1000);
+    assert_eq!(
+        Err(AsciiError::NonAsciiInput("héllo".to_owned())),
+        ascii.send_line("héllo")
+    );
+    ascii.read().unwrap();
+    assert!(matches!(
+        ascii.icc.run_and_get_next(),
+        RunResult::NeedMoreInput
+    ));
+}
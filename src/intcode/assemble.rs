@@ -0,0 +1,433 @@
+use crate::intcode::Instruction;
+use crate::intcode::ParameterMode::{self, ImmediateMode1, PositionMode0, RelativeMode2};
+use std::collections::HashMap;
+use std::fmt;
+
+// text assembly for intcode, in the same syntax the disassembler prints:
+//
+//     loop: add [rb+1], 5, [counter]    ; position operands are [x], relative ones [rb+x]
+//           jnz [counter], loop         ; and immediates are bare numbers or labels
+//           halt
+//     counter: data 0, 0                ; raw cells
+//
+// there are also a few macros that keep a stack on the relative base, with rb pointing at the
+// next free cell: `push x`, `pop [dst]`, `call label` and `ret`, plus `jmp label`. a leading
+// `123:` address like the disassembler prints is ignored, so its output assembles back
+
+#[derive(Debug, PartialEq)]
+pub struct AssembleError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for AssembleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for AssembleError {}
+
+#[derive(Clone, Debug)]
+enum Expr {
+    Number(i128),
+    Label(String, i128),
+}
+
+#[derive(Clone, Debug)]
+struct Operand {
+    mode: ParameterMode,
+    expr: Expr,
+}
+
+#[derive(Debug)]
+enum Statement {
+    Instruction(i128, Vec<Operand>),
+    Data(Vec<Expr>),
+}
+
+impl Statement {
+    fn size(&self) -> usize {
+        match self {
+            Statement::Instruction(_, operands) => 1 + operands.len(),
+            Statement::Data(values) => values.len(),
+        }
+    }
+}
+
+const OPCODES: [i128; 10] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 99];
+
+fn opcode(mnemonic: &str) -> Option<i128> {
+    OPCODES
+        .iter()
+        .cloned()
+        .find(|op| Instruction::parse(*op).mnemonic() == mnemonic)
+}
+
+fn mode_digit(mode: ParameterMode) -> i128 {
+    match mode {
+        PositionMode0 => 0,
+        ImmediateMode1 => 1,
+        RelativeMode2 => 2,
+    }
+}
+
+fn parse_expr(s: &str) -> Result<Expr, String> {
+    let s = s.trim();
+    if let Ok(n) = s.parse() {
+        return Ok(Expr::Number(n));
+    }
+    let (name, offset) = match s.find(&['+', '-'][..]) {
+        Some(i) => {
+            let offset: i128 = s[i + 1..]
+                .trim()
+                .parse()
+                .map_err(|_| format!("bad offset in {:?}", s))?;
+            let sign = if s[i..].starts_with('-') { -1 } else { 1 };
+            (s[..i].trim(), sign * offset)
+        }
+        None => (s, 0),
+    };
+    let is_identifier = name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if !is_identifier {
+        return Err(format!("expected a number or a label, got {:?}", s));
+    }
+    Ok(Expr::Label(name.to_owned(), offset))
+}
+
+fn parse_operand(s: &str) -> Result<Operand, String> {
+    let s = s.trim();
+    if !(s.starts_with('[') && s.ends_with(']')) {
+        return Ok(Operand {
+            mode: ImmediateMode1,
+            expr: parse_expr(s)?,
+        });
+    }
+    let inner = s[1..s.len() - 1].trim();
+    if inner == "rb" {
+        return Ok(Operand {
+            mode: RelativeMode2,
+            expr: Expr::Number(0),
+        });
+    }
+    if inner.starts_with("rb")
+        && inner[2..]
+            .trim_start()
+            .starts_with(&['+', '-'][..])
+    {
+        let rest = inner[2..].trim_start();
+        let expr = parse_expr(&rest[1..])?;
+        let expr = match (rest.starts_with('-'), expr) {
+            (true, Expr::Number(n)) => Expr::Number(-n),
+            (true, Expr::Label(..)) => return Err(format!("can't negate a label in {:?}", s)),
+            (false, expr) => expr,
+        };
+        return Ok(Operand {
+            mode: RelativeMode2,
+            expr,
+        });
+    }
+    Ok(Operand {
+        mode: PositionMode0,
+        expr: parse_expr(inner)?,
+    })
+}
+
+fn parse_operands(s: &str) -> Result<Vec<Operand>, String> {
+    if s.trim().is_empty() {
+        return Ok(vec![]);
+    }
+    s.split(',').map(parse_operand).collect()
+}
+
+fn immediate(expr: Expr) -> Operand {
+    Operand {
+        mode: ImmediateMode1,
+        expr,
+    }
+}
+
+fn relative(offset: i128) -> Operand {
+    Operand {
+        mode: RelativeMode2,
+        expr: Expr::Number(offset),
+    }
+}
+
+fn instruction(mnemonic: &str, operands: Vec<Operand>) -> Result<Statement, String> {
+    let op = opcode(mnemonic).ok_or_else(|| format!("unknown instruction {:?}", mnemonic))?;
+    let arity = Instruction::parse(op).modes().len();
+    if operands.len() != arity {
+        return Err(format!(
+            "{} takes {} operands, got {}",
+            mnemonic,
+            arity,
+            operands.len()
+        ));
+    }
+    Ok(Statement::Instruction(op, operands))
+}
+
+fn one_operand(mnemonic: &str, operands: &[Operand]) -> Result<Operand, String> {
+    match operands {
+        [operand] => Ok(operand.clone()),
+        _ => Err(format!(
+            "{} takes 1 operand, got {}",
+            mnemonic,
+            operands.len()
+        )),
+    }
+}
+
+// a statement, or the handful of statements a macro expands to. `addr` is where they'll start,
+// `call` needs it to work out its return address
+fn parse_statement(mnemonic: &str, operands: &str, addr: usize) -> Result<Vec<Statement>, String> {
+    let operands = parse_operands(operands)?;
+    let zero = || immediate(Expr::Number(0));
+    let one = || immediate(Expr::Number(1));
+    Ok(match mnemonic {
+        "data" => vec![Statement::Data(
+            operands
+                .into_iter()
+                .map(|operand| match operand.mode {
+                    ImmediateMode1 => Ok(operand.expr),
+                    _ => Err("data can only hold plain numbers and labels".to_owned()),
+                })
+                .collect::<Result<_, _>>()?,
+        )],
+        "push" => vec![
+            instruction(
+                "add",
+                vec![one_operand(mnemonic, &operands)?, zero(), relative(0)],
+            )?,
+            instruction("arb", vec![one()])?,
+        ],
+        "pop" => vec![
+            instruction("arb", vec![immediate(Expr::Number(-1))])?,
+            instruction(
+                "add",
+                vec![relative(0), zero(), one_operand(mnemonic, &operands)?],
+            )?,
+        ],
+        "call" => {
+            // add ret, 0, [rb] / arb 1 / jnz 1, target is 4 + 2 + 3 cells
+            let return_addr = Expr::Number(addr as i128 + 9);
+            vec![
+                instruction("add", vec![immediate(return_addr), zero(), relative(0)])?,
+                instruction("arb", vec![one()])?,
+                instruction("jnz", vec![one(), one_operand(mnemonic, &operands)?])?,
+            ]
+        }
+        "ret" if operands.is_empty() => vec![
+            instruction("arb", vec![immediate(Expr::Number(-1))])?,
+            instruction("jnz", vec![one(), relative(0)])?,
+        ],
+        "jmp" => vec![instruction(
+            "jnz",
+            vec![one(), one_operand(mnemonic, &operands)?],
+        )?],
+        _ => vec![instruction(mnemonic, operands)?],
+    })
+}
+
+fn strip_address(line: &str) -> &str {
+    match line.find(':') {
+        Some(i) if !line[..i].trim().is_empty() && line[..i].trim().parse::<usize>().is_ok() => {
+            &line[i + 1..]
+        }
+        _ => line,
+    }
+}
+
+pub fn assemble(source: &str) -> Result<Vec<i128>, AssembleError> {
+    let mut labels = HashMap::new();
+    let mut statements = vec![];
+    let mut addr = 0;
+    for (i, line) in source.lines().enumerate() {
+        let error = |message| AssembleError {
+            line: i + 1,
+            message,
+        };
+        let mut line = strip_address(line.split(';').next().unwrap()).trim();
+        while let Some(colon) = line.find(':') {
+            let name = line[..colon].trim();
+            match parse_expr(name) {
+                Ok(Expr::Label(ref label, 0)) if label == name => {
+                    if labels.insert(name.to_owned(), addr).is_some() {
+                        return Err(error(format!("label {} is defined twice", name)));
+                    }
+                }
+                _ => return Err(error(format!("bad label {:?}", name))),
+            }
+            line = line[colon + 1..].trim();
+        }
+        if line.is_empty() {
+            continue;
+        }
+        let (mnemonic, operands) = match line.find(char::is_whitespace) {
+            Some(space) => (&line[..space], &line[space..]),
+            None => (line, ""),
+        };
+        for statement in parse_statement(mnemonic, operands, addr).map_err(error)? {
+            addr += statement.size();
+            statements.push((i + 1, statement));
+        }
+    }
+
+    let mut proggy = vec![];
+    for (line, statement) in statements {
+        let resolve = |expr: &Expr| match expr {
+            Expr::Number(n) => Ok(*n),
+            Expr::Label(name, offset) => match labels.get(name) {
+                Some(addr) => Ok(*addr as i128 + offset),
+                None => Err(AssembleError {
+                    line,
+                    message: format!("undefined label {}", name),
+                }),
+            },
+        };
+        match statement {
+            Statement::Instruction(op, operands) => {
+                let mut code = op;
+                let mut place = 100;
+                for operand in &operands {
+                    code += mode_digit(operand.mode) * place;
+                    place *= 10;
+                }
+                proggy.push(code);
+                for operand in &operands {
+                    proggy.push(resolve(&operand.expr)?);
+                }
+            }
+            Statement::Data(values) => {
+                for value in &values {
+                    proggy.push(resolve(value)?);
+                }
+            }
+        }
+    }
+    Ok(proggy)
+}
+
+#[test]
+fn assembles_day5_examples() {
+    use crate::intcode::parse_proggy;
+
+    let equal_to_8 = "
+        in [input]
+        eq [input], [eight], [input]
+        out [input]
+        halt
+        input: data -1
+        eight: data 8
+    ";
+    assert_eq!(
+        parse_proggy("3,9,8,9,10,9,4,9,99,-1,8"),
+        assemble(equal_to_8).unwrap()
+    );
+
+    let jump_test = "
+        in [input]
+        jz [input], [target]
+        add [output], [one], [output]
+        is_zero: out [output]
+        halt
+        input: data -1
+        output: data 0
+        one: data 1
+        target: data is_zero
+    ";
+    assert_eq!(
+        parse_proggy("3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9"),
+        assemble(jump_test).unwrap()
+    );
+}
+
+#[test]
+fn assembles_day9_examples() {
+    use crate::intcode::parse_proggy;
+
+    let quine = "
+        arb 1
+        start: out [rb-1]
+        add [100], 1, [100]
+        eq [100], 16, [101]
+        jz [101], 0
+        halt
+    ";
+    assert_eq!(
+        parse_proggy("109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99"),
+        assemble(quine).unwrap()
+    );
+
+    let big_multiply = "
+        mul 34915192, 34915192, [result]
+        out [result]
+        halt
+        result: data 0
+    ";
+    assert_eq!(
+        parse_proggy("1102,34915192,34915192,7,4,7,99,0"),
+        assemble(big_multiply).unwrap()
+    );
+}
+
+#[test]
+fn stack_macros_run() {
+    use crate::intcode::IntCodeComputer;
+
+    let proggy = assemble(
+        "
+        arb stack
+        in [rb]
+        arb 1
+        call double
+        pop [result]
+        out [result]
+        halt
+
+        double: arb -1          ; return address is on top, the argument under it
+        pop [scratch]
+        mul [scratch], 2, [scratch]
+        push [scratch]
+        arb 1
+        ret
+
+        result: data 0
+        scratch: data 0
+        stack: data 0
+        ",
+    )
+    .unwrap();
+    let mut icc = IntCodeComputer::from_ints(proggy);
+    icc.queue_input(21);
+    assert_eq!(vec![42], icc.run_until_halt());
+}
+
+#[test]
+fn reassembles_disassembled_boost() {
+    use crate::intcode::disassemble::disassemble;
+    use crate::intcode::parse_proggy;
+
+    let boost = parse_proggy(include_str!("../../input/2019/day9.txt"));
+    assert_eq!(boost, assemble(&disassemble(&boost)).unwrap());
+}
+
+#[test]
+fn reports_errors_with_line_numbers() {
+    assert_eq!(
+        Err(AssembleError {
+            line: 2,
+            message: "undefined label nowhere".to_owned()
+        }),
+        assemble("halt\njmp nowhere")
+    );
+    assert_eq!(
+        Err(AssembleError {
+            line: 1,
+            message: "add takes 3 operands, got 2".to_owned()
+        }),
+        assemble("add 1, 2")
+    );
+}
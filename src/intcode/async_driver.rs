@@ -0,0 +1,339 @@
+use crate::intcode::{IntCodeComputer, RunResult};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+
+// driving machines from async code. a machine that runs out of input awaits more instead of
+// handing NeedMoreInput back, and its outputs come out as a stream. all of it's single threaded:
+// machines talk through channels that are just a shared queue, and the executor below runs
+// whichever machine has something to do while the rest wait
+
+// the same shape as the futures crate's Stream, so things can be hooked up to that if it's ever
+// pulled in
+pub trait Stream {
+    type Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>>;
+
+    fn next(&mut self) -> Next<'_, Self>
+    where
+        Self: Unpin + Sized,
+    {
+        Next(self)
+    }
+}
+
+pub struct Next<'a, S>(&'a mut S);
+
+impl<'a, S: Stream + Unpin> Future for Next<'a, S> {
+    type Output = Option<S::Item>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        Pin::new(&mut *self.0).poll_next(cx)
+    }
+}
+
+struct Queue {
+    values: VecDeque<i128>,
+    senders: usize,
+    // whoever's waiting on the receiving end
+    waker: Option<Waker>,
+}
+
+impl Queue {
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+pub struct Sender {
+    queue: Rc<RefCell<Queue>>,
+}
+
+// receiving ends once every sender's gone and everything that was sent has been received
+pub struct Receiver {
+    queue: Rc<RefCell<Queue>>,
+}
+
+pub fn channel() -> (Sender, Receiver) {
+    let queue = Rc::new(RefCell::new(Queue {
+        values: VecDeque::new(),
+        senders: 1,
+        waker: None,
+    }));
+    (
+        Sender {
+            queue: queue.clone(),
+        },
+        Receiver { queue },
+    )
+}
+
+impl Sender {
+    pub fn send(&self, value: i128) {
+        let mut queue = self.queue.borrow_mut();
+        queue.values.push_back(value);
+        queue.wake();
+    }
+}
+
+impl Clone for Sender {
+    fn clone(&self) -> Self {
+        self.queue.borrow_mut().senders += 1;
+        Self {
+            queue: self.queue.clone(),
+        }
+    }
+}
+
+impl Drop for Sender {
+    fn drop(&mut self) {
+        let mut queue = self.queue.borrow_mut();
+        queue.senders -= 1;
+        if queue.senders == 0 {
+            queue.wake();
+        }
+    }
+}
+
+impl Receiver {
+    // doesn't wait, None if there's nothing there right now
+    pub fn try_recv(&mut self) -> Option<i128> {
+        self.queue.borrow_mut().values.pop_front()
+    }
+
+    pub async fn recv(&mut self) -> Option<i128> {
+        self.next().await
+    }
+}
+
+impl Stream for Receiver {
+    type Item = i128;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<i128>> {
+        let mut queue = self.queue.borrow_mut();
+        match queue.values.pop_front() {
+            Some(value) => Poll::Ready(Some(value)),
+            None if queue.senders == 0 => Poll::Ready(None),
+            None => {
+                queue.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+// a machine's outputs, as it makes them. it gets its input from a stream, and when the machine
+// stops, stopped says why: Halt, Error, or NeedMoreInput if the input stream ended
+pub struct Outputs<'a, I> {
+    icc: &'a mut IntCodeComputer,
+    input: &'a mut I,
+    pub stopped: Option<RunResult>,
+}
+
+impl<'a, I: Stream<Item = i128> + Unpin> Stream for Outputs<'a, I> {
+    type Item = i128;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<i128>> {
+        let outputs = self.get_mut();
+        if outputs.stopped.is_some() {
+            return Poll::Ready(None);
+        }
+        loop {
+            match outputs.icc.run_and_get_next() {
+                RunResult::Output(value) => return Poll::Ready(Some(value)),
+                RunResult::NeedMoreInput => match Pin::new(&mut *outputs.input).poll_next(cx) {
+                    Poll::Ready(Some(input)) => outputs.icc.queue_input(input),
+                    Poll::Ready(None) => {
+                        outputs.stopped = Some(RunResult::NeedMoreInput);
+                        return Poll::Ready(None);
+                    }
+                    // the input instruction runs again next time we're polled
+                    Poll::Pending => return Poll::Pending,
+                },
+                stopped => {
+                    outputs.stopped = Some(stopped);
+                    return Poll::Ready(None);
+                }
+            }
+        }
+    }
+}
+
+impl IntCodeComputer {
+    pub fn outputs<'a, I: Stream<Item = i128> + Unpin>(
+        &'a mut self,
+        input: &'a mut I,
+    ) -> Outputs<'a, I> {
+        Outputs {
+            icc: self,
+            input,
+            stopped: None,
+        }
+    }
+
+    // runs the machine with its input and output hooked up to channels, until it halts, errors
+    // or its input's senders all go away
+    pub async fn run_async(&mut self, input: &mut Receiver, output: &Sender) -> RunResult {
+        let mut outputs = self.outputs(input);
+        while let Some(value) = outputs.next().await {
+            output.send(value);
+        }
+        outputs.stopped.unwrap()
+    }
+}
+
+// lets the other tasks have a go before carrying on
+pub async fn yield_now() {
+    let mut yielded = false;
+    std::future::poll_fn(|cx| {
+        if yielded {
+            Poll::Ready(())
+        } else {
+            yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    })
+    .await
+}
+
+type Task = Pin<Box<dyn Future<Output = ()>>>;
+
+// None is the future block_on is waiting for, Some is a spawned task
+type TaskId = Option<usize>;
+
+struct TaskWaker {
+    task: TaskId,
+    ready: Arc<Mutex<VecDeque<TaskId>>>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.ready.lock().unwrap().push_back(self.task);
+    }
+}
+
+// runs tasks one at a time, in the order they were woken up. nothing gets polled unless it was
+// woken, so waiting machines cost nothing
+#[derive(Default)]
+pub struct Executor {
+    tasks: Vec<Option<Task>>,
+    ready: Arc<Mutex<VecDeque<TaskId>>>,
+}
+
+impl Executor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn spawn(&mut self, task: impl Future<Output = ()> + 'static) {
+        self.tasks.push(Some(Box::pin(task)));
+        self.ready
+            .lock()
+            .unwrap()
+            .push_back(Some(self.tasks.len() - 1));
+    }
+
+    fn waker(&self, task: TaskId) -> Waker {
+        Waker::from(Arc::new(TaskWaker {
+            task,
+            ready: self.ready.clone(),
+        }))
+    }
+
+    fn next_ready(&self) -> Option<TaskId> {
+        self.ready.lock().unwrap().pop_front()
+    }
+
+    fn poll_task(&mut self, id: usize) {
+        let waker = self.waker(Some(id));
+        if let Some(task) = self.tasks[id].as_mut() {
+            if task
+                .as_mut()
+                .poll(&mut Context::from_waker(&waker))
+                .is_ready()
+            {
+                self.tasks[id] = None;
+            }
+        }
+    }
+
+    // runs until there's nothing left that can make progress, and gives back how many tasks are
+    // still waiting on something
+    pub fn run_until_stalled(&mut self) -> usize {
+        while let Some(task) = self.next_ready() {
+            if let Some(id) = task {
+                self.poll_task(id);
+            }
+        }
+        self.tasks.iter().filter(|task| task.is_some()).count()
+    }
+
+    // runs the spawned tasks alongside future until it finishes. whatever's still running after
+    // that is left for later
+    pub fn block_on<T>(&mut self, future: impl Future<Output = T>) -> T {
+        let mut future = Box::pin(future);
+        let waker = self.waker(None);
+        self.ready.lock().unwrap().push_back(None);
+        loop {
+            match self.next_ready() {
+                Some(None) => {
+                    if let Poll::Ready(value) =
+                        future.as_mut().poll(&mut Context::from_waker(&waker))
+                    {
+                        return value;
+                    }
+                }
+                Some(Some(id)) => self.poll_task(id),
+                None => panic!("block_on is waiting on something that's never going to happen"),
+            }
+        }
+    }
+}
+
+#[test]
+fn machines_wait_on_each_other() {
+    use crate::intcode::assemble::assemble;
+
+    let add_one = assemble(
+        "
+        loop: in [x]
+        add [x], 1, [x]
+        out [x]
+        jmp loop
+        x: data 0
+        ",
+    )
+    .unwrap();
+    let mut executor = Executor::new();
+    let (to_first, mut first_input) = channel();
+    let (first_output, mut second_input) = channel();
+    let (second_output, mut results) = channel();
+    let mut first = IntCodeComputer::from_ints(add_one.to_vec());
+    let mut second = IntCodeComputer::from_ints(add_one.to_vec());
+    executor.spawn(async move {
+        let result = first.run_async(&mut first_input, &first_output).await;
+        assert!(matches!(result, RunResult::NeedMoreInput));
+    });
+    executor.spawn(async move {
+        let result = second.run_async(&mut second_input, &second_output).await;
+        assert!(matches!(result, RunResult::NeedMoreInput));
+    });
+
+    assert_eq!(2, executor.run_until_stalled());
+    assert_eq!(None, results.try_recv());
+    to_first.send(1);
+    to_first.send(10);
+    assert_eq!(Some(3), executor.block_on(results.recv()));
+    assert_eq!(Some(12), executor.block_on(results.recv()));
+    drop(to_first);
+    assert_eq!(0, executor.run_until_stalled());
+    assert_eq!(None, executor.block_on(results.recv()));
+}
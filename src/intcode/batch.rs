@@ -0,0 +1,65 @@
+use crate::intcode::{IntCodeComputer, IntcodeError};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+impl IntCodeComputer {
+    // runs the machine to the end once for every set of input, in parallel on rayon's threads, and
+    // gives back what each run printed in the same order the inputs came in. each run gets its own
+    // clone of the machine, which shares every page of memory with the rest until it writes to it,
+    // so the program isn't copied for each one. any input already queued on the machine goes first
+    pub fn run_batch<I: AsRef<[i128]> + Sync>(
+        &self,
+        inputs: &[I],
+    ) -> Vec<Result<Vec<i128>, IntcodeError>> {
+        inputs
+            .par_iter()
+            .map(|input| {
+                let mut icc = self.clone();
+                for &value in input.as_ref() {
+                    icc.queue_input(value);
+                }
+                icc.try_run_until_halt()
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn batches_come_back_in_order() {
+    use crate::intcode::assemble::assemble;
+    use crate::intcode::IntcodeErrorKind;
+
+    // adds up the two numbers it's given, and prints the sum and the first one
+    let proggy = assemble(
+        "
+        in [a]
+        in [b]
+        add [a], [b], [b]
+        out [b]
+        out [a]
+        halt
+        a: data 0
+        b: data 0
+        ",
+    )
+    .unwrap();
+    let icc = IntCodeComputer::from_ints(proggy);
+    let inputs = (0..1000).map(|i| vec![i, 2 * i]).collect::<Vec<_>>();
+    let outputs = icc.run_batch(&inputs);
+    assert_eq!(1000, outputs.len());
+    for (i, output) in (0..).zip(outputs) {
+        assert_eq!(vec![3 * i, i], output.unwrap());
+    }
+    // the machine it was run on didn't go anywhere
+    assert_eq!(0, icc.num_instructions_processed);
+
+    // a run that goes wrong doesn't stop the others, and input that's already queued goes first
+    let mut icc = icc;
+    icc.queue_input(10);
+    let outputs = icc.run_batch(&[vec![1], vec![], vec![-4]]);
+    assert_eq!(vec![11, 10], *outputs[0].as_ref().unwrap());
+    assert_eq!(
+        IntcodeErrorKind::RanOutOfInput,
+        outputs[1].as_ref().unwrap_err().kind
+    );
+    assert_eq!(vec![6, 10], *outputs[2].as_ref().unwrap());
+}
@@ -0,0 +1,210 @@
+use crate::intcode::disassemble::{analyze, decode_at, format_instruction, jump_edges, label};
+use crate::intcode::Instruction::{Halt99, JumpIfFalse6, JumpIfTrue5};
+use crate::intcode::ParameterMode::ImmediateMode1;
+use itertools::Itertools;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Edge {
+    // a jump with an immediate target
+    Jump(usize),
+    // on to the next instruction, because the last one doesn't jump or its jump wasn't taken
+    FallThrough(usize),
+    // a jump whose target comes out of memory, so it could go anywhere. returns look like this
+    Unknown,
+}
+
+// a run of instructions that always execute together: only the first one is jumped to and only
+// the last one jumps
+#[derive(Debug)]
+pub struct BasicBlock {
+    pub start: usize,
+    // addresses of the instructions in the block
+    pub instructions: Vec<usize>,
+    pub edges: Vec<Edge>,
+}
+
+#[derive(Debug)]
+pub struct ControlFlowGraph {
+    pub blocks: BTreeMap<usize, BasicBlock>,
+}
+
+fn ends_block(proggy: &[i128], addr: usize) -> bool {
+    matches!(
+        decode_at(proggy, addr),
+        Some((JumpIfTrue5(..), _)) | Some((JumpIfFalse6(..), _)) | Some((Halt99, _))
+    )
+}
+
+// splits the code the disassembler finds into basic blocks, without running anything. a block
+// starts at address 0, at every jump target, after every jump, and wherever code starts again
+// after data
+pub fn control_flow_graph(proggy: &[i128]) -> ControlFlowGraph {
+    let analysis = analyze(proggy);
+    let mut leaders = analysis.jump_targets.clone();
+    leaders.insert(0);
+    for (addr, instruction) in &analysis.instructions {
+        if ends_block(proggy, *addr) {
+            leaders.insert(addr + instruction.size());
+        }
+    }
+    let falls_into = analysis
+        .instructions
+        .iter()
+        .map(|(addr, instruction)| addr + instruction.size())
+        .collect::<BTreeSet<_>>();
+    leaders.extend(
+        analysis
+            .instructions
+            .keys()
+            .filter(|addr| !falls_into.contains(addr)),
+    );
+
+    let mut blocks = BTreeMap::new();
+    for &start in leaders
+        .iter()
+        .filter(|addr| analysis.instructions.contains_key(addr))
+    {
+        let mut instructions = vec![start];
+        let mut addr = start;
+        loop {
+            let next = addr + analysis.instructions[&addr].size();
+            if ends_block(proggy, addr)
+                || leaders.contains(&next)
+                || !analysis.instructions.contains_key(&next)
+            {
+                break;
+            }
+            instructions.push(next);
+            addr = next;
+        }
+
+        let (instruction, operands) = decode_at(proggy, addr).unwrap();
+        let next = addr + instruction.size();
+        let edges = match instruction {
+            JumpIfTrue5(_, target_mode) | JumpIfFalse6(_, target_mode) => {
+                let condition_is_written = analysis.written.contains(&(addr + 1));
+                let (target, falls_through) =
+                    jump_edges(&instruction, operands, condition_is_written);
+                let mut edges = vec![];
+                match target {
+                    Some(target) => edges.push(Edge::Jump(target)),
+                    None if target_mode != ImmediateMode1 => edges.push(Edge::Unknown),
+                    // never taken, or a negative target that would just error
+                    None => (),
+                }
+                if falls_through {
+                    edges.push(Edge::FallThrough(next));
+                }
+                edges
+            }
+            Halt99 => vec![],
+            _ => vec![Edge::FallThrough(next)],
+        };
+        blocks.insert(
+            start,
+            BasicBlock {
+                start,
+                instructions,
+                edges,
+            },
+        );
+    }
+    ControlFlowGraph { blocks }
+}
+
+impl ControlFlowGraph {
+    // graphviz, e.g. `cfg day9.txt | dot -Tsvg > day9.svg`. each block lists its instructions,
+    // taken jumps are solid, fall throughs are dashed and jumps to somewhere we can't tell go to
+    // a node of their own
+    pub fn to_dot(&self, proggy: &[i128]) -> String {
+        let labelled = self.blocks.keys().cloned().collect::<BTreeSet<_>>();
+        let mut lines = vec![
+            "digraph cfg {".to_owned(),
+            "    node [shape=box, fontname=\"monospace\"];".to_owned(),
+        ];
+        let mut any_unknown = false;
+        for block in self.blocks.values() {
+            let body = block
+                .instructions
+                .iter()
+                .map(|addr| {
+                    let (instruction, operands) = decode_at(proggy, *addr).unwrap();
+                    format!(
+                        "{:>6}: {}\\l",
+                        addr,
+                        format_instruction(&instruction, operands, &labelled)
+                    )
+                })
+                .join("");
+            lines.push(format!(
+                "    {} [label=\"{}:\\l{}\"];",
+                label(block.start),
+                label(block.start),
+                body
+            ));
+            for edge in &block.edges {
+                lines.push(match edge {
+                    Edge::Jump(target) => {
+                        format!("    {} -> {};", label(block.start), label(*target))
+                    }
+                    Edge::FallThrough(next) => format!(
+                        "    {} -> {} [style=dashed];",
+                        label(block.start),
+                        label(*next)
+                    ),
+                    Edge::Unknown => {
+                        any_unknown = true;
+                        format!("    {} -> unknown [color=red];", label(block.start))
+                    }
+                });
+            }
+        }
+        if any_unknown {
+            lines.push("    unknown [shape=ellipse, label=\"indirect jump\"];".to_owned());
+        }
+        lines.push("}".to_owned());
+        lines.join("\n")
+    }
+}
+
+#[test]
+fn splits_into_blocks_at_jumps_and_their_targets() {
+    // the input lands on the jump's condition, so both ways out of it are code
+    let proggy = [3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1];
+    let cfg = control_flow_graph(&proggy);
+    assert_eq!(
+        vec![
+            (0, vec![0, 2], vec![Edge::Jump(9), Edge::FallThrough(5)]),
+            (5, vec![5], vec![Edge::FallThrough(9)]),
+            (9, vec![9, 11], vec![]),
+        ],
+        cfg.blocks
+            .values()
+            .map(|block| (block.start, block.instructions.clone(), block.edges.clone()))
+            .collect_vec()
+    );
+    assert_eq!(
+        r#"digraph cfg {
+    node [shape=box, fontname="monospace"];
+    L0 [label="L0:\l     0: in   [3]\l     2: jnz  -1, L9\l"];
+    L0 -> L9;
+    L0 -> L5 [style=dashed];
+    L5 [label="L5:\l     5: add  0, 0, [12]\l"];
+    L5 -> L9 [style=dashed];
+    L9 [label="L9:\l     9: out  [12]\l    11: halt\l"];
+}"#,
+        cfg.to_dot(&proggy)
+    );
+}
+
+#[test]
+fn jumps_through_memory_are_unknown_edges() {
+    // push 7 as the return address, call 9, which returns through it
+    let proggy = [21101, 7, 0, 0, 1105, 1, 9, 99, 0, 2105, 1, 0];
+    let cfg = control_flow_graph(&proggy);
+    assert_eq!(vec![Edge::Jump(9)], cfg.blocks[&0].edges);
+    assert_eq!(Vec::<Edge>::new(), cfg.blocks[&7].edges);
+    assert_eq!(vec![Edge::Unknown], cfg.blocks[&9].edges);
+    assert!(cfg.to_dot(&proggy).contains("L9 -> unknown"));
+}
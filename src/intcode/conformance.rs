@@ -0,0 +1,330 @@
+use crate::intcode::arithmetic::BigIntCodeComputer;
+use crate::intcode::async_driver::{channel, Executor, Stream};
+use crate::intcode::history::History;
+use crate::intcode::trace::NoTrace;
+use crate::intcode::transpile::{
+    day19_beam, day9_boost, overflows, overflows_proggy, patches_itself, Compiled, Machine,
+};
+use crate::intcode::{parse_proggy, IntCodeComputer, IntcodeErrorKind, RunResult};
+use num::ToPrimitive;
+use std::sync::{Arc, Mutex};
+
+// programs with known outputs that every way of running intcode in the crate has to agree on,
+// each implementation gets a test at the bottom
+
+struct Case {
+    name: String,
+    proggy: Vec<i128>,
+    input: Vec<i128>,
+    output: Vec<i128>,
+    // what it stops with after that output, None if it halts
+    error: Option<IntcodeErrorKind>,
+}
+
+fn case(name: &str, proggy: &[i128], input: &[i128], output: &[i128]) -> Case {
+    Case {
+        name: name.to_owned(),
+        proggy: proggy.to_vec(),
+        input: input.to_vec(),
+        output: output.to_vec(),
+        error: None,
+    }
+}
+
+fn overflow_cases() -> Vec<Case> {
+    let overflows = |name: &str, input: &[i128], output: &[i128]| Case {
+        error: Some(IntcodeErrorKind::Overflow),
+        ..case(name, &overflows_proggy(), input, output)
+    };
+    vec![
+        case("no overflow", &overflows_proggy(), &[3, 4], &[7, 12]),
+        overflows("add overflows", &[i128::MAX, 1], &[]),
+        overflows("mul overflows", &[1 << 64, 1 << 64], &[1 << 65]),
+        overflows(
+            "relative base overflows",
+            &[1 << 63, 1 << 63],
+            &[1 << 64, 1 << 126],
+        ),
+    ]
+}
+
+// day5's examples that output 1 if their input passes a test and 0 if it doesn't
+type Comparison = (&'static str, &'static [i128], fn(i128) -> bool);
+
+fn day5_cases() -> Vec<Case> {
+    let comparisons: &[Comparison] = &[
+        (
+            "equal to 8, position mode",
+            &[3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8],
+            |i| i == 8,
+        ),
+        (
+            "less than 8, position mode",
+            &[3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8],
+            |i| i < 8,
+        ),
+        (
+            "equal to 8, immediate mode",
+            &[3, 3, 1108, -1, 8, 3, 4, 3, 99],
+            |i| i == 8,
+        ),
+        (
+            "less than 8, immediate mode",
+            &[3, 3, 1107, -1, 8, 3, 4, 3, 99],
+            |i| i < 8,
+        ),
+        (
+            "jumps, position mode",
+            &[3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9],
+            |i| i != 0,
+        ),
+        (
+            "jumps, immediate mode",
+            &[3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1],
+            |i| i != 0,
+        ),
+    ];
+    let around_8 = parse_proggy(
+        "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,1002,21,125,20,4,\
+         20,1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99",
+    );
+    let mut cases = vec![];
+    for input in -1..=9 {
+        for (name, proggy, expected) in comparisons {
+            let name = format!("day5 {} with {}", name, input);
+            cases.push(case(&name, proggy, &[input], &[expected(input) as i128]));
+        }
+        let name = format!("day5 999, 1000 or 1001 around 8 with {}", input);
+        let output = 1000 + (input - 8).signum();
+        cases.push(case(&name, &around_8, &[input], &[output]));
+    }
+    cases
+}
+
+fn cases() -> Vec<Case> {
+    let quine = [
+        109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ];
+    let boost = parse_proggy(include_str!("../../input/2019/day9.txt"));
+    let mut cases = day5_cases();
+    cases.extend(vec![
+        case("day9 quine", &quine, &[], &quine),
+        case(
+            "day9 16 digit number",
+            &[1102, 34915192, 34915192, 7, 4, 7, 99, 0],
+            &[],
+            &[1219070632396864],
+        ),
+        case(
+            "day9 large number",
+            &[104, 1125899906842624, 99],
+            &[],
+            &[1125899906842624],
+        ),
+        case("day9 BOOST test mode", &boost, &[1], &[3839402290]),
+        case(
+            "relative mode writes",
+            &[
+                109, 100, 21101, 3, 4, -1, 203, 1, 22201, -1, 1, 2, 204, 2, 99,
+            ],
+            &[10],
+            &[17],
+        ),
+        case(
+            "relative base can go down",
+            &[109, 20, 109, -15, 204, -1, 99],
+            &[],
+            &[204],
+        ),
+        case(
+            "reads past the end are 0",
+            &[1, 1000, 1001, 1002, 4, 1002, 4, 100000, 99],
+            &[],
+            &[0, 0],
+        ),
+        case(
+            "writes past the end read back",
+            &[1101, 5, 6, 5000, 4, 5000, 99],
+            &[],
+            &[11],
+        ),
+        case(
+            "writes over an operand it's about to use",
+            &[1101, 0, 42, 5, 104, 0, 99],
+            &[],
+            &[42],
+        ),
+        case(
+            "writes over an opcode it's about to run",
+            &[1101, 0, 99, 6, 104, 1, 4, 0],
+            &[],
+            &[1],
+        ),
+        case(
+            "input over its own code through the relative base",
+            &[109, 5, 203, 0, 104, 0, 99],
+            &[42],
+            &[42],
+        ),
+        case(
+            "writes over code it already ran, then goes back to it",
+            &[1106, 0, 6, 104, 7, 99, 1101, 0, 8, 4, 1105, 1, 3],
+            &[],
+            &[8],
+        ),
+    ]);
+    cases.extend(overflow_cases());
+    cases
+}
+
+// everything a run printed, and the result it stopped with
+fn collect(mut next: impl FnMut() -> RunResult) -> (Vec<i128>, RunResult) {
+    let mut output = vec![];
+    loop {
+        match next() {
+            RunResult::Output(value) => output.push(value),
+            stopped => return (output, stopped),
+        }
+    }
+}
+
+// runs every case that run knows how to, and panics with the ones that came out wrong. gives back
+// how many were run
+fn check(run: impl Fn(&Case) -> Option<(Vec<i128>, RunResult)>) -> usize {
+    let mut ran = 0;
+    let mut failures = vec![];
+    for case in cases() {
+        if let Some((output, stopped)) = run(&case) {
+            ran += 1;
+            let error = match stopped {
+                RunResult::Halt => None,
+                RunResult::Error(e) => Some(e.kind),
+                RunResult::NeedMoreInput => Some(IntcodeErrorKind::RanOutOfInput),
+                RunResult::Output(_) => unreachable!(),
+            };
+            if (&output, error) != (&case.output, case.error) {
+                failures.push(format!(
+                    "{}: expected {:?} then {:?} but got {:?} then {:?}",
+                    case.name, case.output, case.error, output, error
+                ));
+            }
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    ran
+}
+
+fn interpreter(case: &Case) -> IntCodeComputer {
+    let mut icc = IntCodeComputer::from_ints(case.proggy.clone());
+    for &input in &case.input {
+        icc.queue_input(input);
+    }
+    icc
+}
+
+#[test]
+fn interpreter_conforms() {
+    check(|case| Some(interpreter(case).run_and_collect_all_output()));
+}
+
+#[test]
+fn interpreter_without_the_decode_cache_conforms() {
+    check(|case| {
+        let mut icc = interpreter(case);
+        icc.disable_decode_cache();
+        Some(icc.run_and_collect_all_output())
+    });
+}
+
+#[test]
+fn traced_interpreter_conforms() {
+    check(|case| {
+        let mut icc = interpreter(case);
+        icc.trace_to(Arc::new(Mutex::new(NoTrace)));
+        Some(icc.run_and_collect_all_output())
+    });
+}
+
+#[test]
+fn recorded_history_conforms() {
+    check(|case| {
+        let mut history = History::new(IntCodeComputer::from_ints(case.proggy.clone()));
+        for &input in &case.input {
+            history.queue_input(input);
+        }
+        Some(collect(|| history.run_and_get_next()))
+    });
+}
+
+#[test]
+fn io_device_conforms() {
+    check(|case| {
+        let mut input = case.input.iter().copied();
+        let mut output = vec![];
+        let result = IntCodeComputer::from_ints(case.proggy.clone())
+            .run_with(&mut (|| input.next(), |value| output.push(value)));
+        Some((output, result))
+    });
+}
+
+#[test]
+fn async_driver_conforms() {
+    check(|case| {
+        let (to_icc, mut input) = channel();
+        for &value in &case.input {
+            to_icc.send(value);
+        }
+        drop(to_icc);
+        let mut icc = IntCodeComputer::from_ints(case.proggy.clone());
+        Executor::new().block_on(async {
+            let mut outputs = icc.outputs(&mut input);
+            let mut output = vec![];
+            while let Some(value) = outputs.next().await {
+                output.push(value);
+            }
+            Some((output, outputs.stopped.take().unwrap()))
+        })
+    });
+}
+
+#[test]
+fn big_cells_conform() {
+    check(|case| {
+        // big cells don't overflow, that's what they're for
+        if case.error.is_some() {
+            return None;
+        }
+        let mut icc = BigIntCodeComputer::from_ints(case.proggy.clone());
+        for &input in &case.input {
+            icc.queue_input(input);
+        }
+        let output = icc.run_until_halt();
+        let output = output
+            .iter()
+            .map(|value| value.to_i128().unwrap())
+            .collect();
+        Some((output, RunResult::Halt))
+    });
+}
+
+// only programs that were transpiled ahead of time can run compiled
+#[test]
+fn transpiled_programs_conform() {
+    let compiled: [&'static Compiled; 4] = [
+        &day9_boost::PROGRAM,
+        &day19_beam::PROGRAM,
+        &patches_itself::PROGRAM,
+        &overflows::PROGRAM,
+    ];
+    let ran = check(|case| {
+        let program = compiled
+            .iter()
+            .find(|program| program.proggy == &case.proggy[..])?;
+        let mut machine = Machine::new(program);
+        for &input in &case.input {
+            machine.queue_input(input);
+        }
+        Some(collect(|| machine.run_and_get_next()))
+    });
+    assert_eq!(6, ran);
+}
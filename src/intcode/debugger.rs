@@ -0,0 +1,511 @@
+use crate::intcode::disassemble::format_instruction;
+use crate::intcode::trace::NoTrace;
+use crate::intcode::Instruction::Input3;
+use crate::intcode::{IntCodeComputer, IntcodeError, RunResult};
+use itertools::Itertools;
+use std::collections::BTreeSet;
+use std::fmt;
+
+// a step debugger wrapped around a machine. it runs one instruction at a time and checks the
+// breakpoints before each one, so a machine that's being debugged is a lot slower than one that
+// isn't. everything the machine outputs ends up in `output`.
+//
+// `command` takes the same commands the debug binary reads from stdin, `help` lists them
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Breakpoint {
+    // about to run the instruction at this address
+    Address(usize),
+    // about to run an input instruction
+    Input,
+    // an instruction just output something
+    Output,
+    // about to write to this address
+    Write(usize),
+    Condition(Condition),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Value {
+    Pc,
+    Rb,
+    Number(i128),
+    // [n], or [rb+n] when relative
+    Cell { addr: i128, relative: bool },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+const COMPARISONS: [(&str, Comparison); 6] = [
+    ("==", Comparison::Eq),
+    ("!=", Comparison::Ne),
+    ("<=", Comparison::Le),
+    (">=", Comparison::Ge),
+    ("<", Comparison::Lt),
+    (">", Comparison::Gt),
+];
+
+// e.g. `[rb+1] == 5` or `pc > 100`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Condition {
+    pub lhs: Value,
+    pub comparison: Comparison,
+    pub rhs: Value,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Stop {
+    // index of the breakpoint that was hit
+    Breakpoint(usize),
+    // ran as many steps as were asked for
+    Stepped,
+    NeedMoreInput,
+    Halt,
+    Error(IntcodeError),
+}
+
+pub struct Debugger {
+    pub icc: IntCodeComputer,
+    pub output: Vec<i128>,
+    // deleted breakpoints leave a hole so the others keep their numbers
+    breakpoints: Vec<Option<Breakpoint>>,
+}
+
+impl Value {
+    fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        match s {
+            "pc" => return Ok(Value::Pc),
+            "rb" => return Ok(Value::Rb),
+            _ => (),
+        }
+        if s.starts_with('[') && s.ends_with(']') {
+            let inner = s[1..s.len() - 1].trim();
+            let (addr, relative) = match inner.strip_prefix("rb") {
+                Some("") => ("0".to_owned(), true),
+                Some(offset) => (offset.replace(' ', "").replace("+", ""), true),
+                None => (inner.to_owned(), false),
+            };
+            let addr = addr.parse().map_err(|_| format!("bad address in {}", s))?;
+            return Ok(Value::Cell { addr, relative });
+        }
+        s.parse()
+            .map(Value::Number)
+            .map_err(|_| format!("expected pc, rb, a number or a memory cell, got {}", s))
+    }
+
+    fn eval(&self, icc: &IntCodeComputer) -> i128 {
+        match *self {
+            Value::Pc => icc.current_pos as i128,
+            Value::Rb => icc.relative_base,
+            Value::Number(n) => n,
+            Value::Cell { addr, relative } => {
+                let addr = if relative {
+                    addr.saturating_add(icc.relative_base)
+                } else {
+                    addr
+                };
+                IntCodeComputer::addr(addr).map_or(0, |addr| icc.memory[addr])
+            }
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Value::Pc => write!(f, "pc"),
+            Value::Rb => write!(f, "rb"),
+            Value::Number(n) => write!(f, "{}", n),
+            Value::Cell {
+                addr,
+                relative: false,
+            } => write!(f, "[{}]", addr),
+            Value::Cell { addr, .. } if addr < 0 => write!(f, "[rb-{}]", addr.unsigned_abs()),
+            Value::Cell { addr, .. } => write!(f, "[rb+{}]", addr),
+        }
+    }
+}
+
+impl Condition {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let (op, comparison) = COMPARISONS
+            .iter()
+            .find(|(op, _)| s.contains(op))
+            .ok_or_else(|| format!("no comparison in {}", s))?;
+        let i = s.find(op).unwrap();
+        Ok(Self {
+            lhs: Value::parse(&s[..i])?,
+            comparison: *comparison,
+            rhs: Value::parse(&s[i + op.len()..])?,
+        })
+    }
+
+    fn holds(&self, icc: &IntCodeComputer) -> bool {
+        let (lhs, rhs) = (self.lhs.eval(icc), self.rhs.eval(icc));
+        match self.comparison {
+            Comparison::Eq => lhs == rhs,
+            Comparison::Ne => lhs != rhs,
+            Comparison::Lt => lhs < rhs,
+            Comparison::Le => lhs <= rhs,
+            Comparison::Gt => lhs > rhs,
+            Comparison::Ge => lhs >= rhs,
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (op, _) = COMPARISONS
+            .iter()
+            .find(|(_, comparison)| *comparison == self.comparison)
+            .unwrap();
+        write!(f, "{} {} {}", self.lhs, op, self.rhs)
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Breakpoint::Address(addr) => write!(f, "at {}", addr),
+            Breakpoint::Input => write!(f, "on input"),
+            Breakpoint::Output => write!(f, "on output"),
+            Breakpoint::Write(addr) => write!(f, "on write to [{}]", addr),
+            Breakpoint::Condition(condition) => write!(f, "if {}", condition),
+        }
+    }
+}
+
+const HELP: &str = "\
+s, step [n]        run n instructions, 1 if there's no n
+c, continue        run until a breakpoint, an input with no input queued, or a halt
+b, break <addr>    break before running the instruction at addr
+b in               break before any input instruction
+b out              break after any output
+w, watch <addr>    break before anything writes to addr
+b if <condition>   break once the condition holds, e.g. b if [rb+1] >= 10, or b if pc == rb
+d, delete <n>      delete breakpoint n
+l, list            list breakpoints
+r, regs            show pc, rb, the input queue and the next instruction
+x <addr> [len]     show len cells of memory starting at addr
+set <addr> <v>     write v to memory, or set pc <v> / set rb <v>
+i, input <v, ...>  queue some input
+a, ascii <text>    queue a line of text as ascii, with a newline at the end";
+
+impl Debugger {
+    pub fn new(icc: IntCodeComputer) -> Self {
+        Self {
+            icc,
+            output: vec![],
+            breakpoints: vec![],
+        }
+    }
+
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) -> usize {
+        self.breakpoints.push(Some(breakpoint));
+        self.breakpoints.len() - 1
+    }
+
+    pub fn remove_breakpoint(&mut self, n: usize) -> Option<Breakpoint> {
+        self.breakpoints.get_mut(n)?.take()
+    }
+
+    pub fn step(&mut self, n: usize) -> Stop {
+        self.run_for(Some(n))
+    }
+
+    pub fn resume(&mut self) -> Stop {
+        self.run_for(None)
+    }
+
+    // the first instruction always runs, so resuming from a breakpoint doesn't just hit it again
+    fn run_for(&mut self, max_steps: Option<usize>) -> Stop {
+        let mut steps = 0;
+        loop {
+            if max_steps == Some(steps) {
+                return Stop::Stepped;
+            }
+            if steps > 0 {
+                if let Some(n) = self.breakpoint_before_step() {
+                    return Stop::Breakpoint(n);
+                }
+            }
+            if let Some(stop) = self.execute() {
+                return stop;
+            }
+            steps += 1;
+        }
+    }
+
+    fn execute(&mut self) -> Option<Stop> {
+        match self.icc.run_one_traced()? {
+            RunResult::Output(value) => {
+                self.output.push(value);
+                self.find_breakpoint(|bp| *bp == Breakpoint::Output)
+                    .map(Stop::Breakpoint)
+            }
+            RunResult::NeedMoreInput => Some(Stop::NeedMoreInput),
+            RunResult::Halt => Some(Stop::Halt),
+            RunResult::Error(e) => Some(Stop::Error(e)),
+        }
+    }
+
+    fn find_breakpoint(&self, pred: impl Fn(&Breakpoint) -> bool) -> Option<usize> {
+        self.breakpoints
+            .iter()
+            .position(|bp| matches!(bp, Some(bp) if pred(bp)))
+    }
+
+    fn breakpoint_before_step(&mut self) -> Option<usize> {
+        let pc = self.icc.current_pos;
+        let decoded = self.icc.decode_cache.get(&self.icc.memory, pc);
+        let is_input = matches!(decoded.map(|d| d.instruction), Some(Input3(_)));
+        let write = self.icc.next_write();
+        let icc = &self.icc;
+        self.find_breakpoint(|bp| match bp {
+            Breakpoint::Address(addr) => *addr == pc,
+            Breakpoint::Input => is_input,
+            Breakpoint::Output => false,
+            Breakpoint::Write(addr) => write == Some(*addr),
+            Breakpoint::Condition(condition) => condition.holds(icc),
+        })
+    }
+
+    // pc, rb, what's queued up for input, and the instruction that runs next
+    pub fn registers(&mut self) -> String {
+        let pc = self.icc.current_pos;
+        let next = match self.icc.decode_cache.get(&self.icc.memory, pc) {
+            Some(decoded) => format_instruction(
+                &decoded.instruction,
+                &decoded.operands[..decoded.instruction.size() - 1],
+                &BTreeSet::new(),
+            ),
+            None => format!("data {}", self.icc.memory[pc]),
+        };
+        format!(
+            "pc {}  rb {}  input [{}]  steps {}\n{:>6}: {}",
+            pc,
+            self.icc.relative_base,
+            self.icc.input.iter().rev().join(", "),
+            self.icc.num_instructions_processed,
+            pc,
+            next
+        )
+    }
+
+    pub fn memory(&self, start: usize, len: usize) -> String {
+        (start..start.saturating_add(len))
+            .chunks(8)
+            .into_iter()
+            .map(|chunk| {
+                let chunk = chunk.collect_vec();
+                let values = chunk.iter().map(|addr| self.icc.memory[*addr]).join(" ");
+                format!("{:>6}: {}", chunk[0], values)
+            })
+            .join("\n")
+    }
+
+    // runs one line of debugger input and returns what to print for it
+    pub fn command(&mut self, line: &str) -> Result<String, String> {
+        let line = line.trim();
+        let (command, args) = match line.find(' ') {
+            Some(i) => (&line[..i], line[i + 1..].trim()),
+            None => (line, ""),
+        };
+        let int = |s: &str| {
+            s.trim()
+                .parse::<i128>()
+                .map_err(|_| format!("expected a number, got {:?}", s))
+        };
+        let addr = |s: &str| {
+            s.trim()
+                .parse::<usize>()
+                .map_err(|_| format!("expected an address, got {:?}", s))
+        };
+        let output_before = self.output.len();
+        let stop = match command {
+            "" => return Ok(String::new()),
+            "h" | "help" => return Ok(HELP.to_owned()),
+            "s" | "step" => {
+                let n = if args.is_empty() { 1 } else { addr(args)? };
+                self.step(n)
+            }
+            "c" | "continue" => self.resume(),
+            "b" | "break" => {
+                let breakpoint = match args {
+                    "in" => Breakpoint::Input,
+                    "out" => Breakpoint::Output,
+                    _ if args.starts_with("if ") => {
+                        Breakpoint::Condition(Condition::parse(&args[3..])?)
+                    }
+                    _ => Breakpoint::Address(addr(args)?),
+                };
+                let message = format!("breakpoint {} {}", self.breakpoints.len(), breakpoint);
+                self.add_breakpoint(breakpoint);
+                return Ok(message);
+            }
+            "w" | "watch" => {
+                let breakpoint = Breakpoint::Write(addr(args)?);
+                let message = format!("breakpoint {} {}", self.breakpoints.len(), breakpoint);
+                self.add_breakpoint(breakpoint);
+                return Ok(message);
+            }
+            "d" | "delete" => {
+                return match self.remove_breakpoint(addr(args)?) {
+                    Some(breakpoint) => Ok(format!("deleted breakpoint {}", breakpoint)),
+                    None => Err(format!("no breakpoint {}", args)),
+                };
+            }
+            "l" | "list" => {
+                return Ok(self
+                    .breakpoints
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(n, bp)| Some(format!("{}: {}", n, bp.as_ref()?)))
+                    .join("\n"))
+            }
+            "r" | "regs" => return Ok(self.registers()),
+            "x" => {
+                let mut args = args.split_whitespace();
+                let start = addr(args.next().unwrap_or(""))?;
+                let len = args.next().map_or(Ok(8), addr)?;
+                return Ok(self.memory(start, len));
+            }
+            "set" => {
+                let mut args = args.split_whitespace();
+                let target = args.next().unwrap_or("");
+                let value = int(args.next().unwrap_or(""))?;
+                match target {
+                    "pc" => {
+                        self.icc.current_pos = IntCodeComputer::addr(value)
+                            .map_err(|_| format!("can't set pc to {}", value))?
+                    }
+                    "rb" => self.icc.relative_base = value,
+                    _ => self.icc.write(addr(target)?, value, &mut NoTrace),
+                }
+                return Ok(String::new());
+            }
+            "i" | "input" => {
+                for value in args.split(',') {
+                    self.icc.queue_input(int(value)?);
+                }
+                return Ok(String::new());
+            }
+            "a" | "ascii" => {
+                for chr in args.chars().chain(std::iter::once('\n')) {
+                    self.icc.queue_input(chr as i128);
+                }
+                return Ok(String::new());
+            }
+            _ => return Err(format!("unknown command {:?}, try help", command)),
+        };
+
+        let mut lines = vec![];
+        let new_output = &self.output[output_before..];
+        if !new_output.is_empty() {
+            // anything that prints whole lines of ascii (day21, day25) is probably text
+            if new_output.contains(&10) && new_output.iter().all(|c| (0..128).contains(c)) {
+                let text = new_output.iter().map(|c| *c as u8 as char).join("");
+                lines.push(text.trim_end_matches('\n').to_owned());
+            } else {
+                lines.push(format!("output {}", new_output.iter().join(", ")));
+            }
+        }
+        match stop {
+            Stop::Breakpoint(n) => lines.push(format!(
+                "hit breakpoint {} {}",
+                n,
+                self.breakpoints[n].as_ref().unwrap()
+            )),
+            Stop::Stepped => (),
+            Stop::NeedMoreInput => lines.push("waiting for input".to_owned()),
+            Stop::Halt => lines.push("halted".to_owned()),
+            Stop::Error(e) => lines.push(e.to_string()),
+        }
+        lines.push(self.registers());
+        Ok(lines.join("\n"))
+    }
+}
+
+#[test]
+fn breaks_on_addresses_writes_and_conditions() {
+    // counts [20] up from 0 to 3, outputting it every time round
+    let proggy = vec![
+        1001, 20, 1, 20, // 0: add [20], 1, [20]
+        4, 20, // 4: out [20]
+        1007, 20, 3, 21, // 6: lt [20], 3, [21]
+        1005, 21, 0,  // 10: jnz [21], 0
+        99, // 13: halt
+    ];
+    let mut debugger = Debugger::new(IntCodeComputer::from_ints(proggy));
+    debugger.add_breakpoint(Breakpoint::Address(6));
+    assert_eq!(Stop::Breakpoint(0), debugger.resume());
+    assert_eq!(6, debugger.icc.current_pos);
+    assert_eq!(vec![1], debugger.output);
+
+    debugger.remove_breakpoint(0);
+    debugger.add_breakpoint(Breakpoint::Write(21));
+    assert_eq!(Stop::Breakpoint(1), debugger.resume());
+    assert_eq!(6, debugger.icc.current_pos);
+    assert_eq!(vec![1, 2], debugger.output);
+
+    debugger.remove_breakpoint(1);
+    debugger.add_breakpoint(Breakpoint::Condition(
+        Condition::parse("[20] >= 3").unwrap(),
+    ));
+    assert_eq!(Stop::Breakpoint(2), debugger.resume());
+    assert_eq!(4, debugger.icc.current_pos);
+
+    debugger.remove_breakpoint(2);
+    assert_eq!(Stop::Halt, debugger.resume());
+    assert_eq!(vec![1, 2, 3], debugger.output);
+}
+
+#[test]
+fn commands_edit_memory_and_queue_input() {
+    // in [9], out [9], halt
+    let proggy = vec![3, 9, 4, 9, 99, 0, 0, 0, 0, 0];
+    let mut debugger = Debugger::new(IntCodeComputer::from_ints(proggy));
+    let output = debugger.command("c").unwrap();
+    assert!(output.starts_with("waiting for input\npc 0  rb 0  input []"));
+
+    debugger.command("input 5, 6").unwrap();
+    assert!(debugger
+        .command("r")
+        .unwrap()
+        .starts_with("pc 0  rb 0  input [5, 6]"));
+    debugger.command("b in").unwrap();
+    debugger.command("s").unwrap();
+    debugger.command("set 9 41").unwrap();
+    assert_eq!("     8: 0 41", debugger.command("x 8 2").unwrap());
+    assert!(debugger
+        .command("c")
+        .unwrap()
+        .starts_with("output 41\nhalted"));
+
+    assert!(debugger.command("b if [rb+1] = 2").is_err());
+    assert!(debugger.command("frobnicate").is_err());
+}
+
+#[test]
+fn the_edges_of_memory_print() {
+    let debugger = Debugger::new(IntCodeComputer::from_ints(vec![99]));
+    let top = usize::MAX - 1;
+    assert_eq!(format!("{:>6}: 0", top), debugger.memory(top, 10));
+    let cell = Value::Cell {
+        addr: i128::MIN,
+        relative: true,
+    };
+    assert_eq!(
+        "[rb-170141183460469231731687303715884105728]",
+        cell.to_string()
+    );
+}
@@ -0,0 +1,131 @@
+use crate::intcode::{Instruction, Memory};
+use std::sync::Arc;
+
+// an instruction along with the raw values of the cells that follow it. instructions take at most
+// three operands, shorter ones just carry whatever happens to be in the cells after them
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Decoded {
+    pub instruction: Instruction,
+    pub operands: [i128; 3],
+}
+
+// one slot per address of the program image, filled in the first time an address runs with the
+// instruction and its operands both. writing to a cell throws away the entry of any instruction
+// that cell is part of, its opcode or one of its operands, and it gets decoded again the next time
+// it runs. self modifying programs still do the right thing that way.
+//
+// the table is shared between clones of a machine (day15 clones one per step), in chunks so that
+// when one of them writes over decoded code or runs code nobody decoded yet only that chunk gets
+// copied. the default, empty cache decodes every step.
+#[derive(Clone, Debug, Default)]
+pub struct DecodeCache {
+    chunks: Arc<Vec<Arc<Vec<Option<Decoded>>>>>,
+}
+
+// 64 entries of 64 bytes, the same 4k a page of memory is
+const CHUNK_SIZE: usize = 64;
+
+fn decode(memory: &Memory, addr: usize) -> Option<Decoded> {
+    Some(Decoded {
+        instruction: Instruction::try_parse(memory[addr])?,
+        operands: memory.operands(addr),
+    })
+}
+
+impl DecodeCache {
+    pub fn new(memory: &Memory) -> Self {
+        let chunks = (0..memory.dense_len())
+            .step_by(CHUNK_SIZE)
+            .map(|start| Arc::new(vec![None; (memory.dense_len() - start).min(CHUNK_SIZE)]))
+            .collect();
+        Self {
+            chunks: Arc::new(chunks),
+        }
+    }
+
+    fn entry(&self, addr: usize) -> Option<&Option<Decoded>> {
+        self.chunks.get(addr / CHUNK_SIZE)?.get(addr % CHUNK_SIZE)
+    }
+
+    fn set(&mut self, addr: usize, decoded: Option<Decoded>) {
+        let chunk = &mut Arc::make_mut(&mut self.chunks)[addr / CHUNK_SIZE];
+        Arc::make_mut(chunk)[addr % CHUNK_SIZE] = decoded;
+    }
+
+    // None if there's no valid instruction at addr
+    #[inline(always)]
+    pub fn get(&mut self, memory: &Memory, addr: usize) -> Option<Decoded> {
+        match self.entry(addr) {
+            Some(Some(decoded)) => Some(*decoded),
+            Some(None) => {
+                let decoded = decode(memory, addr)?;
+                self.set(addr, Some(decoded));
+                Some(decoded)
+            }
+            // running outside of the program image, don't bother caching
+            None => decode(memory, addr),
+        }
+    }
+
+    // addr was written to, so it's no longer the opcode or operand any entry says it is. the
+    // instruction it belongs to starts at most three cells before it
+    #[inline(always)]
+    pub fn invalidate(&mut self, addr: usize) {
+        for start in addr.saturating_sub(3)..=addr {
+            if let Some(Some(decoded)) = self.entry(start) {
+                if addr - start < decoded.instruction.size() {
+                    self.set(start, None);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn write_into_code_redecodes() {
+    use crate::intcode::Instruction::{Halt99, Output4};
+    use crate::intcode::ParameterMode::ImmediateMode1;
+
+    // 104,5 outputs 5, 99 halts
+    let mut memory = Memory::new(vec![104, 5, 99]);
+    let mut cache = DecodeCache::new(&memory);
+    assert_eq!(Output4(ImmediateMode1), cache.get(&memory, 0).unwrap().instruction);
+    assert_eq!([5, 99, 0], cache.get(&memory, 0).unwrap().operands);
+
+    memory[1] = 7;
+    cache.invalidate(1);
+    assert_eq!([7, 99, 0], cache.get(&memory, 0).unwrap().operands);
+
+    // 99 isn't part of the output instruction, even though it's cached as one of its operands
+    memory[2] = 98;
+    cache.invalidate(2);
+    assert_eq!([7, 99, 0], cache.get(&memory, 0).unwrap().operands);
+    memory[2] = 99;
+
+    assert_eq!(Halt99, cache.get(&memory, 2).unwrap().instruction);
+    memory[2] = 104;
+    cache.invalidate(2);
+    assert_eq!(Output4(ImmediateMode1), cache.get(&memory, 2).unwrap().instruction);
+}
+
+#[test]
+fn clones_share_the_table_until_one_writes_over_code() {
+    // the output instruction again, with a second one a chunk further on
+    let mut proggy = vec![104, 5, 99];
+    proggy.resize(CHUNK_SIZE, 0);
+    proggy.extend(&[104, 6, 99]);
+    let memory = Memory::new(proggy);
+    let mut cache = DecodeCache::new(&memory);
+    cache.get(&memory, 0);
+    cache.get(&memory, CHUNK_SIZE);
+    let other = cache.clone();
+    assert!(Arc::ptr_eq(&cache.chunks, &other.chunks));
+
+    cache.invalidate(2);
+    assert!(Arc::ptr_eq(&cache.chunks, &other.chunks));
+
+    cache.invalidate(1);
+    assert!(!Arc::ptr_eq(&cache.chunks[0], &other.chunks[0]));
+    assert!(Arc::ptr_eq(&cache.chunks[1], &other.chunks[1]));
+    assert!(other.entry(0).unwrap().is_some());
+}
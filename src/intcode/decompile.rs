@@ -0,0 +1,939 @@
+use crate::intcode::cfg::{control_flow_graph, Edge};
+use crate::intcode::disassemble::{analyze, decode_at, label, moved_constant};
+use crate::intcode::Instruction::{
+    Add1, Equals8, Halt99, Input3, JumpIfFalse6, JumpIfTrue5, LessThan7, Multiply2, Output4,
+    RelativeBaseOffset9,
+};
+use crate::intcode::ParameterMode::{ImmediateMode1, PositionMode0, RelativeMode2};
+use itertools::Itertools;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+// turns a program into c-ish pseudocode, one function per call target plus main, without running
+// anything. it's built on the control flow graph:
+//
+// - a call is a jump made right after the address following it was written somewhere, that's the
+//   return address being pushed. the writes to [rb+1], [rb+2]... just before it are the args
+// - the relative base is tracked through each function, so a relative cell gets the same name
+//   wherever it's used: sN is N cells above where rb was when the function was called. s0 holds
+//   the return address, and jumping through it is a return. main starts with rb at 0, so its
+//   relative cells are plain memory. where rb can't be worked out the cells stay as rb[N]
+// - loops come from back edges to blocks that dominate them, if/else from conditional jumps,
+//   with the else rejoining at the branch's immediate post dominator. whatever doesn't fit that
+//   shape is a goto
+// - position cells are mem[N]. operands the program writes over are read from memory, so a
+//   write through a pointer the program patched in looks like mem[mem[566]]
+pub fn decompile(proggy: &[i128]) -> String {
+    let decompiler = Decompiler::new(proggy);
+    let mut params = BTreeMap::new();
+    params.insert(0, None);
+    for block in decompiler.blocks.values() {
+        if let Some(callee) = block.call {
+            let num_args = params.entry(callee).or_insert(Some(0));
+            *num_args = (*num_args).max(Some(block.args.len()));
+        }
+    }
+    params
+        .into_iter()
+        .map(|(entry, num_params)| {
+            let name = match num_params {
+                None => "main".to_owned(),
+                Some(_) => label(entry),
+            };
+            let params = (1..=num_params.unwrap_or(0) as i128)
+                .map(stack_variable)
+                .join(", ");
+            let mut lines = vec![format!("fn {}({}) {{", name, params)];
+            let mut function = Function::new(&decompiler, entry, num_params.is_none());
+            let body = function.body();
+            render(&body, 1, &function.gotos, &mut lines);
+            lines.push("}".to_owned());
+            lines.join("\n")
+        })
+        .join("\n\n")
+}
+
+fn stack_variable(offset: i128) -> String {
+    if offset < 0 {
+        format!("s_{}", offset.unsigned_abs())
+    } else {
+        format!("s{}", offset)
+    }
+}
+
+// where the relative base is at some point in a function
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Rb {
+    // this many cells from where it was when the function was called
+    Frame(i128),
+    // at this address, in main
+    Absolute(i128),
+    Unknown,
+}
+
+impl Rb {
+    fn moved(self, by: Option<i128>) -> Rb {
+        match (self, by) {
+            (Rb::Frame(rb), Some(by)) => rb.checked_add(by).map_or(Rb::Unknown, Rb::Frame),
+            (Rb::Absolute(rb), Some(by)) => rb.checked_add(by).map_or(Rb::Unknown, Rb::Absolute),
+            _ => Rb::Unknown,
+        }
+    }
+}
+
+// how far below 0 a constant is, so adding it can be printed as a subtraction. None for constants
+// that aren't negative, or are too negative to flip
+fn minus(constant: Option<i128>) -> Option<i128> {
+    constant.filter(|constant| *constant < 0)?.checked_neg()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Exit {
+    // carries on at another block, after falling through, an unconditional jump or a call
+    Goto(usize),
+    // (taken, not taken)
+    Branch(usize, usize),
+    // an indirect jump that falls through when it isn't taken
+    IndirectBranch(usize),
+    // halts, or jumps somewhere we can't tell
+    Stop,
+}
+
+struct Block {
+    // the instructions that become statements, which leaves out the jump or halt at the end and
+    // anything a call swallows
+    body: Vec<usize>,
+    // the jump or halt at the end
+    last: Option<usize>,
+    exit: Exit,
+    call: Option<usize>,
+    // the instructions that set up the call's args, first arg first
+    args: Vec<usize>,
+    // how much the block moves the relative base by, None if it moves by something we can't tell
+    arb: Option<i128>,
+}
+
+struct Decompiler<'a> {
+    proggy: &'a [i128],
+    written: HashSet<usize>,
+    blocks: BTreeMap<usize, Block>,
+}
+
+fn relative_reads(proggy: &[i128], addr: usize) -> Vec<i128> {
+    let (instruction, operands) = decode_at(proggy, addr).unwrap();
+    let modes = instruction.modes();
+    let reads = match instruction {
+        Add1(..) | Multiply2(..) | LessThan7(..) | Equals8(..) => 2,
+        Input3(_) => 0,
+        _ => modes.len(),
+    };
+    modes
+        .into_iter()
+        .zip(operands)
+        .take(reads)
+        .filter(|(mode, _)| *mode == RelativeMode2)
+        .map(|(_, offset)| *offset)
+        .collect()
+}
+
+impl<'a> Decompiler<'a> {
+    fn new(proggy: &'a [i128]) -> Self {
+        let written = analyze(proggy).written;
+        let cfg = control_flow_graph(proggy);
+        let mut blocks = BTreeMap::new();
+        for cfg_block in cfg.blocks.values() {
+            let end = *cfg_block.instructions.last().unwrap();
+            let (instruction, _) = decode_at(proggy, end).unwrap();
+            let next = end + instruction.size();
+            let mut body = cfg_block.instructions.clone();
+            let last = match instruction {
+                JumpIfTrue5(..) | JumpIfFalse6(..) | Halt99 => body.pop(),
+                _ => None,
+            };
+            let mut exit = match cfg_block.edges[..] {
+                [Edge::Jump(target)] | [Edge::FallThrough(target)] => Exit::Goto(target),
+                [Edge::Jump(taken), Edge::FallThrough(not_taken)] => Exit::Branch(taken, not_taken),
+                [Edge::Unknown, Edge::FallThrough(not_taken)] => Exit::IndirectBranch(not_taken),
+                _ => Exit::Stop,
+            };
+
+            let mut call = None;
+            let mut args = vec![];
+            let pushes_return_address = |addr: &usize| {
+                let (instruction, operands) = decode_at(proggy, *addr).unwrap();
+                moved_constant(&instruction, operands) == Some(next as i128)
+            };
+            if let (Some(_), [Edge::Jump(callee)]) = (last, &cfg_block.edges[..]) {
+                if cfg.blocks.contains_key(&next) {
+                    if let Some(push) = body.iter().rposition(pushes_return_address) {
+                        body.remove(push);
+                        call = Some(*callee);
+                        exit = Exit::Goto(next);
+                        args = Self::take_args(proggy, &written, &mut body);
+                    }
+                }
+            }
+
+            let arb = body
+                .iter()
+                .map(|addr| match decode_at(proggy, *addr).unwrap() {
+                    (RelativeBaseOffset9(ImmediateMode1), [offset])
+                        if !written.contains(&(addr + 1)) =>
+                    {
+                        Some(*offset)
+                    }
+                    (RelativeBaseOffset9(_), _) => None,
+                    _ => Some(0),
+                })
+                .try_fold(0i128, |arb, by| arb.checked_add(by?));
+            blocks.insert(
+                cfg_block.start,
+                Block {
+                    body,
+                    last,
+                    exit,
+                    call,
+                    args,
+                    arb,
+                },
+            );
+        }
+        Decompiler {
+            proggy,
+            written,
+            blocks,
+        }
+    }
+
+    // the writes to [rb+1], [rb+2]... at the end of the block, as long as they don't read each
+    // other. only a run starting from [rb+1] counts, anything else stays a statement
+    fn take_args(proggy: &[i128], written: &HashSet<usize>, body: &mut Vec<usize>) -> Vec<usize> {
+        let mut args = BTreeMap::new();
+        while let Some(&addr) = body.last() {
+            let offset = match decode_at(proggy, addr).unwrap() {
+                (Add1(_, _, RelativeMode2), operands)
+                | (Multiply2(_, _, RelativeMode2), operands)
+                | (LessThan7(_, _, RelativeMode2), operands)
+                | (Equals8(_, _, RelativeMode2), operands) => operands[2],
+                _ => break,
+            };
+            if offset < 1
+                || args.contains_key(&offset)
+                || written.contains(&(addr + 3))
+                || args
+                    .values()
+                    .any(|later| relative_reads(proggy, *later).contains(&offset))
+            {
+                break;
+            }
+            args.insert(offset, addr);
+            body.pop();
+        }
+        let num_args = (1..).take_while(|offset| args.contains_key(offset)).count() as i128;
+        let (args, rest): (Vec<_>, Vec<_>) = args
+            .into_iter()
+            .partition(|(offset, _)| *offset <= num_args);
+        body.extend(rest.into_iter().map(|(_, addr)| addr).sorted());
+        args.into_iter().map(|(_, addr)| addr).collect()
+    }
+
+    // nothing comes after an address that isn't code
+    fn successors(&self, block: usize) -> Vec<usize> {
+        let successors = match self.blocks.get(&block).map(|block| block.exit) {
+            Some(Exit::Goto(next)) | Some(Exit::IndirectBranch(next)) => vec![next],
+            Some(Exit::Branch(taken, not_taken)) => vec![taken, not_taken],
+            Some(Exit::Stop) | None => vec![],
+        };
+        successors
+            .into_iter()
+            .filter(|block| self.blocks.contains_key(block))
+            .collect()
+    }
+
+    fn constant(&self, addr: usize, i: usize) -> Option<i128> {
+        let (instruction, operands) = decode_at(self.proggy, addr).unwrap();
+        match instruction.modes()[i] {
+            ImmediateMode1 if !self.written.contains(&(addr + 1 + i)) => Some(operands[i]),
+            _ => None,
+        }
+    }
+
+    fn operand(&self, addr: usize, i: usize, rb: Rb) -> String {
+        let (instruction, operands) = decode_at(self.proggy, addr).unwrap();
+        let cell = addr + 1 + i;
+        let patched = format!("mem[{}]", cell);
+        match (instruction.modes()[i], self.written.contains(&cell)) {
+            (ImmediateMode1, false) => operands[i].to_string(),
+            (ImmediateMode1, true) => patched,
+            (PositionMode0, false) => format!("mem[{}]", operands[i]),
+            (PositionMode0, true) => format!("mem[{}]", patched),
+            (RelativeMode2, true) => format!("rb[{}]", patched),
+            (RelativeMode2, false) => match rb.moved(Some(operands[i])) {
+                Rb::Frame(offset) => stack_variable(offset),
+                Rb::Absolute(addr) => format!("mem[{}]", addr),
+                Rb::Unknown => format!("rb[{}]", operands[i]),
+            },
+        }
+    }
+
+    // the right hand side of add, mul, lt and eq
+    fn expression(&self, addr: usize, rb: Rb) -> String {
+        let (instruction, _) = decode_at(self.proggy, addr).unwrap();
+        let (a, b) = (self.operand(addr, 0, rb), self.operand(addr, 1, rb));
+        let (x, y) = (self.constant(addr, 0), self.constant(addr, 1));
+        // constants that overflow when they're folded get printed as they are
+        let folded = match (instruction, x, y) {
+            (Add1(..), Some(x), Some(y)) => x.checked_add(y),
+            (Multiply2(..), Some(x), Some(y)) => x.checked_mul(y),
+            (LessThan7(..), Some(x), Some(y)) => Some((x < y) as i128),
+            (Equals8(..), Some(x), Some(y)) => Some((x == y) as i128),
+            _ => None,
+        };
+        if let Some(value) = folded {
+            return value.to_string();
+        }
+        match (instruction, x, y, minus(x), minus(y)) {
+            (Add1(..), Some(0), ..) => b,
+            (Add1(..), _, Some(0), ..) => a,
+            (Add1(..), .., Some(y)) => format!("{} - {}", a, y),
+            (Add1(..), _, _, Some(x), _) => format!("{} - {}", b, x),
+            (Add1(..), ..) => format!("{} + {}", a, b),
+            (Multiply2(..), Some(1), ..) => b,
+            (Multiply2(..), _, Some(1), ..) => a,
+            (Multiply2(..), Some(-1), None, ..) => format!("-{}", b),
+            (Multiply2(..), None, Some(-1), ..) => format!("-{}", a),
+            (Multiply2(..), ..) => format!("{} * {}", a, b),
+            (LessThan7(..), ..) => format!("{} < {}", a, b),
+            (Equals8(..), ..) => format!("{} == {}", a, b),
+            _ => unreachable!("{:?} isn't an expression", instruction),
+        }
+    }
+
+    // moves rb along when the instruction is an arb, which only needs a statement of its own when
+    // we've lost track of where rb is
+    fn statement(&self, addr: usize, rb: &mut Rb) -> Option<String> {
+        let (instruction, _) = decode_at(self.proggy, addr).unwrap();
+        Some(match instruction {
+            Add1(..) | Multiply2(..) | LessThan7(..) | Equals8(..) => format!(
+                "{} = {};",
+                self.operand(addr, 2, *rb),
+                self.expression(addr, *rb)
+            ),
+            Input3(_) => format!("{} = input();", self.operand(addr, 0, *rb)),
+            Output4(_) => format!("output({});", self.operand(addr, 0, *rb)),
+            RelativeBaseOffset9(_) => {
+                let arb = self.constant(addr, 0);
+                let statement = match (arb, minus(arb)) {
+                    (Some(_), _) if *rb != Rb::Unknown => None,
+                    (_, Some(by)) => Some(format!("rb -= {};", by)),
+                    _ => Some(format!("rb += {};", self.operand(addr, 0, *rb))),
+                };
+                *rb = rb.moved(arb);
+                return statement;
+            }
+            JumpIfTrue5(..) | JumpIfFalse6(..) | Halt99 => {
+                unreachable!("jumps and halts end blocks")
+            }
+        })
+    }
+
+    // a jump we can't follow: a return if it goes through s0, a computed goto otherwise
+    fn indirect_jump(&self, addr: usize, rb: Rb) -> String {
+        let (instruction, operands) = decode_at(self.proggy, addr).unwrap();
+        match instruction.modes()[1] {
+            RelativeMode2
+                if rb.moved(Some(operands[1])) == Rb::Frame(0)
+                    && !self.written.contains(&(addr + 2)) =>
+            {
+                "return;".to_owned()
+            }
+            _ => format!("goto *{};", self.operand(addr, 1, rb)),
+        }
+    }
+
+    // returns the condition if the block ends in a branch
+    fn block_statements(&self, block: usize, mut rb: Rb, out: &mut Vec<Stmt>) -> Option<String> {
+        let block = &self.blocks[&block];
+        for addr in &block.body {
+            if let Some(statement) = self.statement(*addr, &mut rb) {
+                out.push(Stmt::Line(statement));
+            }
+        }
+        if let Some(callee) = block.call {
+            let args = block.args.iter().map(|arg| self.expression(*arg, rb));
+            out.push(Stmt::Line(format!(
+                "{}({});",
+                label(callee),
+                args.format(", ")
+            )));
+        }
+        let last = block.last?;
+        let condition = match decode_at(self.proggy, last).unwrap().0 {
+            JumpIfTrue5(..) => self.operand(last, 0, rb),
+            JumpIfFalse6(..) => format!("!{}", self.operand(last, 0, rb)),
+            _ => {
+                out.push(Stmt::Jump("halt;".to_owned()));
+                return None;
+            }
+        };
+        match block.exit {
+            Exit::Branch(..) => return Some(condition),
+            Exit::IndirectBranch(_) => out.push(Stmt::Line(format!(
+                "if ({}) {}",
+                condition,
+                self.indirect_jump(last, rb)
+            ))),
+            Exit::Stop => out.push(Stmt::Jump(self.indirect_jump(last, rb))),
+            Exit::Goto(_) => (),
+        }
+        None
+    }
+}
+
+enum Stmt {
+    Line(String),
+    // a line that doesn't carry on to the next one: break, continue, goto, return, halt
+    Jump(String),
+    // only printed if something jumps to it
+    Label(usize),
+    If(String, Vec<Stmt>, Vec<Stmt>),
+    Loop(Vec<Stmt>),
+}
+
+// labels only get printed if something jumps to them, and nothing has yet
+fn is_empty(stmts: &[Stmt]) -> bool {
+    stmts.iter().all(|stmt| matches!(stmt, Stmt::Label(_)))
+}
+
+fn ends_in_jump(stmts: &[Stmt]) -> bool {
+    matches!(stmts.last(), Some(Stmt::Jump(_)))
+}
+
+fn negate(condition: &str) -> String {
+    match condition.strip_prefix('!') {
+        Some(condition) => condition.to_owned(),
+        None => format!("!{}", condition),
+    }
+}
+
+fn render(stmts: &[Stmt], depth: usize, gotos: &HashSet<usize>, lines: &mut Vec<String>) {
+    let indent = "    ".repeat(depth);
+    for stmt in stmts {
+        match stmt {
+            Stmt::Line(line) | Stmt::Jump(line) => lines.push(format!("{}{}", indent, line)),
+            Stmt::Label(block) if gotos.contains(block) => {
+                lines.push(format!("{}{}:", indent, label(*block)))
+            }
+            Stmt::Label(_) => (),
+            Stmt::If(condition, then, otherwise) => {
+                lines.push(format!("{}if ({}) {{", indent, condition));
+                render(then, depth + 1, gotos, lines);
+                if !otherwise.is_empty() {
+                    lines.push(format!("{}}} else {{", indent));
+                    render(otherwise, depth + 1, gotos, lines);
+                }
+                lines.push(format!("{}}}", indent));
+            }
+            Stmt::Loop(body) => {
+                lines.push(format!("{}loop {{", indent));
+                render(body, depth + 1, gotos, lines);
+                lines.push(format!("{}}}", indent));
+            }
+        }
+    }
+}
+
+struct Loop {
+    body: BTreeSet<usize>,
+    // where breaking out goes. any other way out is a goto
+    follow: Option<usize>,
+}
+
+// the blocks reachable from a function's entry, without following calls into their callees
+struct Function<'a> {
+    decompiler: &'a Decompiler<'a>,
+    entry: usize,
+    blocks: BTreeSet<usize>,
+    rb: HashMap<usize, Rb>,
+    post_dominator: HashMap<usize, usize>,
+    loops: HashMap<usize, Loop>,
+    loop_stack: Vec<usize>,
+    emitted: HashSet<usize>,
+    gotos: HashSet<usize>,
+}
+
+impl<'a> Function<'a> {
+    fn new(decompiler: &'a Decompiler<'a>, entry: usize, main: bool) -> Self {
+        let mut blocks = BTreeSet::new();
+        let mut to_visit = vec![entry];
+        while let Some(block) = to_visit.pop() {
+            if decompiler.blocks.contains_key(&block) && blocks.insert(block) {
+                to_visit.extend(decompiler.successors(block));
+            }
+        }
+        let mut function = Function {
+            decompiler,
+            entry,
+            blocks,
+            rb: HashMap::new(),
+            post_dominator: HashMap::new(),
+            loops: HashMap::new(),
+            loop_stack: vec![],
+            emitted: HashSet::new(),
+            gotos: HashSet::new(),
+        };
+        function.track_relative_base(if main { Rb::Absolute(0) } else { Rb::Frame(0) });
+        function.find_post_dominators();
+        function.find_loops();
+        function
+    }
+
+    fn successors(&self, block: usize) -> Vec<usize> {
+        self.decompiler.successors(block)
+    }
+
+    fn predecessors(&self) -> HashMap<usize, Vec<usize>> {
+        let mut predecessors = HashMap::new();
+        for &block in &self.blocks {
+            for successor in self.successors(block) {
+                predecessors
+                    .entry(successor)
+                    .or_insert_with(Vec::new)
+                    .push(block);
+            }
+        }
+        predecessors
+    }
+
+    // calls are assumed to put rb back how they found it. a block reached with two different
+    // relative bases gets Unknown, and so does everything after it
+    fn track_relative_base(&mut self, entry: Rb) {
+        self.rb.insert(self.entry, entry);
+        let mut to_visit = vec![self.entry];
+        while let Some(block) = to_visit.pop() {
+            let arb = self
+                .decompiler
+                .blocks
+                .get(&block)
+                .and_then(|block| block.arb);
+            let rb = self.rb[&block].moved(arb);
+            for successor in self.successors(block) {
+                let rb = match self.rb.get(&successor) {
+                    None => rb,
+                    Some(seen) if *seen == rb || *seen == Rb::Unknown => continue,
+                    Some(_) => Rb::Unknown,
+                };
+                self.rb.insert(successor, rb);
+                to_visit.push(successor);
+            }
+        }
+    }
+
+    // the usual fixpoint. blocks that can't reach a halt or return have no post dominator, so
+    // they're left out of the intersections
+    fn find_post_dominators(&mut self) {
+        let mut reaches_exit = self
+            .blocks
+            .iter()
+            .filter(|block| self.successors(**block).is_empty())
+            .cloned()
+            .collect::<HashSet<_>>();
+        let predecessors = self.predecessors();
+        let mut to_visit = reaches_exit.iter().cloned().collect_vec();
+        while let Some(block) = to_visit.pop() {
+            for predecessor in predecessors.get(&block).into_iter().flatten() {
+                if reaches_exit.insert(*predecessor) {
+                    to_visit.push(*predecessor);
+                }
+            }
+        }
+
+        let mut post_dominators = reaches_exit
+            .iter()
+            .map(|block| {
+                if self.successors(*block).is_empty() {
+                    (*block, [*block].iter().cloned().collect())
+                } else {
+                    (*block, reaches_exit.clone())
+                }
+            })
+            .collect::<HashMap<_, HashSet<_>>>();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for block in self.blocks.iter().rev() {
+                let mut successors = self
+                    .successors(*block)
+                    .into_iter()
+                    .filter(|successor| reaches_exit.contains(successor));
+                let first = match successors.next() {
+                    Some(first) => post_dominators[&first].clone(),
+                    None => continue,
+                };
+                let mut new = successors.fold(first, |all, successor| {
+                    all.intersection(&post_dominators[&successor])
+                        .cloned()
+                        .collect()
+                });
+                new.insert(*block);
+                if new != post_dominators[block] {
+                    post_dominators.insert(*block, new);
+                    changed = true;
+                }
+            }
+        }
+
+        for (block, dominators) in &post_dominators {
+            let strict = dominators
+                .iter()
+                .filter(|dominator| *dominator != block)
+                .cloned()
+                .collect::<HashSet<_>>();
+            if let Some(immediate) = strict.iter().find(|d| post_dominators[d] == strict) {
+                self.post_dominator.insert(*block, *immediate);
+            }
+        }
+    }
+
+    fn find_loops(&mut self) {
+        let predecessors = self.predecessors();
+        let mut dominators = self
+            .blocks
+            .iter()
+            .map(|block| (*block, self.blocks.clone()))
+            .collect::<HashMap<_, _>>();
+        dominators.insert(self.entry, [self.entry].iter().cloned().collect());
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for block in &self.blocks {
+                if *block == self.entry {
+                    continue;
+                }
+                let mut new = predecessors[block]
+                    .iter()
+                    .map(|predecessor| &dominators[predecessor])
+                    .fold(None, |all: Option<BTreeSet<usize>>, dominators| {
+                        Some(match all {
+                            None => dominators.clone(),
+                            Some(all) => all.intersection(dominators).cloned().collect(),
+                        })
+                    })
+                    .unwrap();
+                new.insert(*block);
+                if new != dominators[block] {
+                    dominators.insert(*block, new);
+                    changed = true;
+                }
+            }
+        }
+
+        let mut bodies = BTreeMap::new();
+        for &block in &self.blocks {
+            for header in self.successors(block) {
+                if !dominators[&block].contains(&header) {
+                    continue;
+                }
+                let body = bodies
+                    .entry(header)
+                    .or_insert_with(|| [header].iter().cloned().collect::<BTreeSet<_>>());
+                let mut to_visit = vec![block];
+                while let Some(block) = to_visit.pop() {
+                    if body.insert(block) {
+                        to_visit.extend(&predecessors[&block]);
+                    }
+                }
+            }
+        }
+        for (header, body) in bodies {
+            let exits = body
+                .iter()
+                .flat_map(|block| self.successors(*block))
+                .filter(|successor| !body.contains(successor))
+                .collect::<BTreeSet<_>>();
+            let follow = match self.post_dominator.get(&header) {
+                Some(follow) if exits.contains(follow) => Some(*follow),
+                _ => exits.iter().next().cloned(),
+            };
+            self.loops.insert(header, Loop { body, follow });
+        }
+    }
+
+    fn body(&mut self) -> Vec<Stmt> {
+        let mut stmts = self.region(Some(self.entry), None);
+        // anything left over is only reachable through gotos
+        for block in self.blocks.clone() {
+            if !self.emitted.contains(&block) {
+                self.gotos.insert(block);
+                stmts.append(&mut self.region(Some(block), None));
+            }
+        }
+        stmts
+    }
+
+    fn goto(&mut self, block: usize) -> Stmt {
+        self.gotos.insert(block);
+        Stmt::Jump(format!("goto {};", label(block)))
+    }
+
+    // the statements from next until control gets to stop, or leaves the region some other way
+    fn region(&mut self, mut next: Option<usize>, stop: Option<usize>) -> Vec<Stmt> {
+        let mut stmts = vec![];
+        while let Some(block) = next {
+            if Some(block) == stop {
+                break;
+            }
+            if !self.decompiler.blocks.contains_key(&block) {
+                stmts.push(Stmt::Jump(format!("goto {}; // not code", label(block))));
+                break;
+            }
+            if let Some(i) = self
+                .loop_stack
+                .iter()
+                .rposition(|header| *header == block || self.loops[header].follow == Some(block))
+            {
+                stmts.push(if i < self.loop_stack.len() - 1 {
+                    self.goto(block)
+                } else if self.loop_stack[i] == block {
+                    Stmt::Jump("continue;".to_owned())
+                } else {
+                    Stmt::Jump("break;".to_owned())
+                });
+                break;
+            }
+            if self.emitted.contains(&block) {
+                stmts.push(self.goto(block));
+                break;
+            }
+            if self.loops.contains_key(&block) {
+                self.loop_stack.push(block);
+                let mut body = vec![];
+                let after_header = self.block(block, &mut body);
+                body.append(&mut self.region(after_header, None));
+                self.loop_stack.pop();
+                if let Some(Stmt::Jump(jump)) = body.last() {
+                    if jump == "continue;" {
+                        body.pop();
+                    }
+                }
+                stmts.push(Stmt::Loop(body));
+                next = self.loops[&block].follow;
+                continue;
+            }
+            next = self.block(block, &mut stmts);
+        }
+        stmts
+    }
+
+    // emits a block, and any if/else it ends in. returns where control goes next
+    fn block(&mut self, block: usize, stmts: &mut Vec<Stmt>) -> Option<usize> {
+        self.emitted.insert(block);
+        stmts.push(Stmt::Label(block));
+        let condition = self
+            .decompiler
+            .block_statements(block, self.rb[&block], stmts);
+        match self.decompiler.blocks[&block].exit {
+            Exit::Goto(next) | Exit::IndirectBranch(next) => Some(next),
+            Exit::Stop => None,
+            Exit::Branch(taken, not_taken) => {
+                // a join outside the loop we're in gets reached by breaking out instead
+                let join = self.post_dominator.get(&block).cloned().filter(|join| {
+                    match self.loop_stack.last() {
+                        Some(header) => self.loops[header].body.contains(join),
+                        None => true,
+                    }
+                });
+                let mut condition = condition.unwrap();
+                let mut then = self.region(Some(taken), join);
+                let mut otherwise = self.region(Some(not_taken), join);
+                // put the branch that jumps away in the if, so the other one can follow it
+                // without an else. and a lone continue goes last, where it can be dropped
+                let swap = match (ends_in_jump(&then), ends_in_jump(&otherwise)) {
+                    _ if is_empty(&then) => true,
+                    (true, true) => {
+                        matches!(&then[..], [Stmt::Jump(jump)] if jump == "continue;")
+                            || otherwise.len() < then.len()
+                    }
+                    (false, true) => true,
+                    (false, false) => condition.starts_with('!') && !is_empty(&otherwise),
+                    _ => false,
+                };
+                if swap {
+                    std::mem::swap(&mut then, &mut otherwise);
+                    condition = negate(&condition);
+                }
+                if is_empty(&then) || ends_in_jump(&then) || is_empty(&otherwise) {
+                    if !is_empty(&then) {
+                        stmts.push(Stmt::If(condition, then, vec![]));
+                    } else {
+                        stmts.append(&mut then);
+                    }
+                    stmts.append(&mut otherwise);
+                } else {
+                    stmts.push(Stmt::If(condition, then, otherwise));
+                }
+                join
+            }
+        }
+    }
+}
+
+#[test]
+fn decompiles_day5_examples() {
+    let equal_to_8 = [3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
+    assert_eq!(
+        "fn main() {
+    mem[9] = input();
+    mem[9] = mem[9] == mem[10];
+    output(mem[9]);
+    halt;
+}",
+        decompile(&equal_to_8)
+    );
+
+    let jumps = [3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1];
+    assert_eq!(
+        "fn main() {
+    mem[3] = input();
+    if (!mem[3]) {
+        mem[12] = 0;
+    }
+    output(mem[12]);
+    halt;
+}",
+        decompile(&jumps)
+    );
+
+    let compare_to_8 = [
+        3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0, 0,
+        1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20,
+        1105, 1, 46, 98, 99,
+    ];
+    assert_eq!(
+        "fn main() {
+    mem[21] = input();
+    mem[20] = mem[21] == 8;
+    if (mem[20]) {
+        mem[20] = mem[21] * 125;
+        output(mem[20]);
+    } else {
+        mem[20] = 8 < mem[21];
+        if (mem[20]) {
+            mem[20] = 1001;
+            output(mem[20]);
+        } else {
+            output(999);
+        }
+    }
+    halt;
+}",
+        decompile(&compare_to_8)
+    );
+}
+
+#[test]
+fn decompiles_day9_examples() {
+    let quine = [
+        109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ];
+    assert_eq!(
+        "fn main() {
+    loop {
+        rb += 1;
+        output(rb[-1]);
+        mem[100] = mem[100] + 1;
+        mem[101] = mem[100] == 16;
+        if (mem[101]) {
+            break;
+        }
+    }
+    halt;
+}",
+        decompile(&quine)
+    );
+
+    let sixteen_digits = [1102, 34915192, 34915192, 7, 4, 7, 99, 0];
+    assert_eq!(
+        "fn main() {
+    mem[7] = 1219070632396864;
+    output(mem[7]);
+    halt;
+}",
+        decompile(&sixteen_digits)
+    );
+}
+
+#[test]
+fn recovers_calls_and_stack_variables() {
+    use crate::intcode::assemble::assemble;
+
+    let proggy = assemble(
+        "
+        arb 10
+        in [50]
+        add [50], 0, [rb+1]
+        add 2, 0, [rb+2]
+        add back, 0, [rb+0]
+        jz 0, double
+        back: out [rb+1]
+        halt
+        double: arb 3
+        mul [rb-2], [rb-1], [rb-2]
+        arb -3
+        jnz 1, [rb+0]
+        ",
+    )
+    .unwrap();
+    assert_eq!(
+        "fn main() {
+    mem[50] = input();
+    L22(mem[50], 2);
+    output(mem[11]);
+    halt;
+}
+
+fn L22(s1, s2) {
+    s1 = s1 * s2;
+    return;
+}",
+        decompile(&proggy)
+    );
+}
+
+#[test]
+fn programs_that_dont_start_with_code() {
+    let not_code = "fn main() {
+    goto L0; // not code
+}";
+    assert_eq!(not_code, decompile(&[]));
+    assert_eq!(not_code, decompile(&[0]));
+    assert_eq!(not_code, decompile(&[1, 2]));
+}
+
+#[test]
+fn constants_that_overflow_arent_folded() {
+    let proggy = [
+        1101,
+        i128::MAX,
+        1,
+        11,
+        1102,
+        i128::MIN,
+        -1,
+        11,
+        109,
+        i128::MIN,
+        99,
+    ];
+    assert_eq!(
+        format!(
+            "fn main() {{
+    mem[11] = {max} + 1;
+    mem[11] = {min} * -1;
+    halt;
+}}",
+            max = i128::MAX,
+            min = i128::MIN
+        ),
+        decompile(&proggy)
+    );
+}
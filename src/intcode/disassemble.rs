@@ -0,0 +1,270 @@
+use crate::intcode::Instruction::{
+    Add1, Equals8, Halt99, Input3, JumpIfFalse6, JumpIfTrue5, LessThan7, Multiply2,
+};
+use crate::intcode::ParameterMode::{ImmediateMode1, PositionMode0, RelativeMode2};
+use crate::intcode::{Instruction, ParameterMode};
+use itertools::Itertools;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+// which cells of a program are code, found by following jumps from address 0 without running
+// anything. cells that are never reached are assumed to be data. written is every address some
+// reachable instruction writes to with a position mode param
+#[derive(Debug)]
+pub struct Analysis {
+    pub instructions: BTreeMap<usize, Instruction>,
+    pub jump_targets: BTreeSet<usize>,
+    pub written: HashSet<usize>,
+}
+
+pub fn decode_at(proggy: &[i128], addr: usize) -> Option<(Instruction, &[i128])> {
+    let instruction = Instruction::try_parse(*proggy.get(addr)?)?;
+    let operands = proggy.get(addr + 1..addr + instruction.size())?;
+    Some((instruction, operands))
+}
+
+// where a jump can go: its target if it's an immediate, and whether it can fall through to the
+// next instruction. `jnz 1, x` and `jz 0, x` are how intcode spells an unconditional jump, unless
+// the program writes over the condition
+pub fn jump_edges(
+    instruction: &Instruction,
+    operands: &[i128],
+    condition_is_written: bool,
+) -> (Option<usize>, bool) {
+    let (cond_mode, target_mode, jumps_if_nonzero) = match *instruction {
+        JumpIfTrue5(cond_mode, target_mode) => (cond_mode, target_mode, true),
+        JumpIfFalse6(cond_mode, target_mode) => (cond_mode, target_mode, false),
+        Halt99 => return (None, false),
+        _ => return (None, true),
+    };
+    let taken = match cond_mode {
+        ImmediateMode1 if !condition_is_written => Some((operands[0] != 0) == jumps_if_nonzero),
+        _ => None,
+    };
+    let target = match target_mode {
+        ImmediateMode1 if operands[1] >= 0 && taken != Some(false) => Some(operands[1] as usize),
+        _ => None,
+    };
+    (target, taken != Some(true))
+}
+
+fn static_write_target(instruction: &Instruction, operands: &[i128]) -> Option<usize> {
+    match instruction {
+        Input3(_) | Add1(..) | Multiply2(..) | LessThan7(..) | Equals8(..) => {
+            match (instruction.modes().last(), operands.last()) {
+                (Some(PositionMode0), Some(addr)) if *addr >= 0 => Some(*addr as usize),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// constants moved into memory with `add x, 0, dst` or `mul x, 1, dst`. calls push their return
+// address like that before jumping, so these tell us where code picks up again after a call
+pub fn moved_constant(instruction: &Instruction, operands: &[i128]) -> Option<i128> {
+    match *instruction {
+        Add1(ImmediateMode1, ImmediateMode1, _) if operands[1] == 0 => Some(operands[0]),
+        Add1(ImmediateMode1, ImmediateMode1, _) if operands[0] == 0 => Some(operands[1]),
+        Multiply2(ImmediateMode1, ImmediateMode1, _) if operands[1] == 1 => Some(operands[0]),
+        Multiply2(ImmediateMode1, ImmediateMode1, _) if operands[0] == 1 => Some(operands[1]),
+        _ => None,
+    }
+}
+
+pub fn analyze(proggy: &[i128]) -> Analysis {
+    let mut written = HashSet::new();
+    let mut return_addresses = HashSet::new();
+    loop {
+        let mut analysis = Analysis {
+            instructions: BTreeMap::new(),
+            jump_targets: BTreeSet::new(),
+            written: HashSet::new(),
+        };
+        let mut unfollowed_fallthroughs = vec![];
+        let mut to_visit = vec![0];
+        while let Some(addr) = to_visit.pop() {
+            if analysis.instructions.contains_key(&addr) {
+                continue;
+            }
+            let (instruction, operands) = match decode_at(proggy, addr) {
+                Some(decoded) => decoded,
+                None => continue,
+            };
+            analysis.instructions.insert(addr, instruction);
+            let next = addr + instruction.size();
+            let (target, falls_through) =
+                jump_edges(&instruction, operands, written.contains(&(addr + 1)));
+            if let Some(target) = target {
+                analysis.jump_targets.insert(target);
+                to_visit.push(target);
+            }
+            if falls_through || return_addresses.contains(&(next as i128)) {
+                to_visit.push(next);
+            } else {
+                unfollowed_fallthroughs.push(next);
+            }
+        }
+
+        let new_written = analysis
+            .instructions
+            .iter()
+            .filter_map(|(addr, instruction)| {
+                let (_, operands) = decode_at(proggy, *addr).unwrap();
+                static_write_target(instruction, operands)
+            })
+            .collect::<HashSet<_>>();
+        let new_return_addresses = analysis
+            .instructions
+            .iter()
+            .filter_map(|(addr, instruction)| {
+                let (_, operands) = decode_at(proggy, *addr).unwrap();
+                moved_constant(instruction, operands)
+            })
+            .filter(|constant| unfollowed_fallthroughs.contains(&(*constant as usize)))
+            .collect::<HashSet<_>>();
+        if new_written.is_subset(&written) && new_return_addresses.is_subset(&return_addresses) {
+            analysis.written = written;
+            return analysis;
+        }
+        written.extend(new_written);
+        return_addresses.extend(new_return_addresses);
+    }
+}
+
+pub fn label(addr: usize) -> String {
+    format!("L{}", addr)
+}
+
+pub fn format_operand(mode: ParameterMode, value: i128, is_label: bool) -> String {
+    match mode {
+        PositionMode0 => format!("[{}]", value),
+        ImmediateMode1 if is_label => label(value as usize),
+        ImmediateMode1 => value.to_string(),
+        RelativeMode2 if value < 0 => format!("[rb-{}]", value.unsigned_abs()),
+        RelativeMode2 => format!("[rb+{}]", value),
+    }
+}
+
+pub fn format_instruction(
+    instruction: &Instruction,
+    operands: &[i128],
+    jump_targets: &BTreeSet<usize>,
+) -> String {
+    let is_jump = matches!(instruction, JumpIfTrue5(..) | JumpIfFalse6(..));
+    let params = instruction
+        .modes()
+        .into_iter()
+        .zip(operands)
+        .enumerate()
+        .map(|(i, (mode, value))| {
+            let is_label =
+                is_jump && i == 1 && *value >= 0 && jump_targets.contains(&(*value as usize));
+            format_operand(mode, *value, is_label)
+        })
+        .join(", ");
+    format!("{:<4} {}", instruction.mnemonic(), params)
+        .trim_end()
+        .to_owned()
+}
+
+const DATA_PER_LINE: usize = 8;
+
+// splits the program into lines: one per instruction, and runs of up to DATA_PER_LINE cells of
+// data that stop at the next instruction or jump target
+fn line_starts(proggy: &[i128], analysis: &Analysis) -> Vec<(usize, usize)> {
+    let mut lines = vec![];
+    let mut addr = 0;
+    while addr < proggy.len() {
+        let len = match analysis.instructions.get(&addr) {
+            Some(instruction) => instruction.size(),
+            None => (addr..proggy.len())
+                .take(DATA_PER_LINE)
+                .take_while(|a| {
+                    *a == addr
+                        || !(analysis.instructions.contains_key(a)
+                            || analysis.jump_targets.contains(a))
+                })
+                .count(),
+        };
+        lines.push((addr, len));
+        addr += len;
+    }
+    lines
+}
+
+// one line per instruction, with the address, the instruction and the raw cells it came from.
+// position operands look like [12], relative ones like [rb+3], immediates are bare numbers
+// except for jump targets, which get a label
+pub fn disassemble(proggy: &[i128]) -> String {
+    let analysis = analyze(proggy);
+    let lines = line_starts(proggy, &analysis);
+    // a jump into the middle of another instruction can't have a label line of its own
+    let labelled = lines
+        .iter()
+        .map(|(addr, _)| *addr)
+        .filter(|addr| analysis.jump_targets.contains(addr))
+        .collect::<BTreeSet<_>>();
+    let mut listing = vec![];
+    for (addr, len) in lines {
+        if labelled.contains(&addr) {
+            listing.push(format!("{}:", label(addr)));
+        }
+        let cells = &proggy[addr..addr + len];
+        match analysis.instructions.get(&addr) {
+            Some(instruction) => listing.push(format!(
+                "{:>6}: {:<32} ; {}",
+                addr,
+                format_instruction(instruction, &cells[1..], &labelled),
+                cells.iter().join(",")
+            )),
+            None => listing.push(format!("{:>6}: data {}", addr, cells.iter().join(", "))),
+        }
+    }
+    listing.join("\n")
+}
+
+#[test]
+fn disassembles_day5_compare_example() {
+    let proggy = [3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
+    assert_eq!(
+        "     0: in   [9]                         ; 3,9
+     2: eq   [9], [10], [9]              ; 8,9,10,9
+     6: out  [9]                         ; 4,9
+     8: halt                             ; 99
+     9: data -1, 8",
+        disassemble(&proggy)
+    );
+}
+
+#[test]
+fn labels_jump_targets() {
+    // the input lands on the jump's condition, so both ways out of it are code
+    let proggy = [3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1];
+    assert_eq!(
+        "     0: in   [3]                         ; 3,3
+     2: jnz  -1, L9                      ; 1105,-1,9
+     5: add  0, 0, [12]                  ; 1101,0,0,12
+L9:
+     9: out  [12]                        ; 4,12
+    11: halt                             ; 99
+    12: data 1",
+        disassemble(&proggy)
+    );
+}
+
+#[test]
+fn follows_calls_back_to_their_return_address() {
+    // push 7 as the return address, call 9, which returns through it
+    let proggy = [21101, 7, 0, 0, 1105, 1, 9, 99, 0, 2105, 1, 0];
+    let analysis = analyze(&proggy);
+    assert!(analysis.instructions.contains_key(&7));
+    assert!(!analysis.instructions.contains_key(&8));
+    assert_eq!(vec![9], analysis.jump_targets.into_iter().collect_vec());
+}
+
+#[test]
+fn the_most_negative_relative_operand() {
+    // out [rb-2^127]
+    let listing = disassemble(&[204, i128::MIN, 99]);
+    assert!(listing.starts_with("     0: out  [rb-170141183460469231731687303715884105728]"));
+}
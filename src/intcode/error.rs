@@ -0,0 +1,53 @@
+use crate::intcode::limits::Limit;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IntcodeErrorKind {
+    // the cell at pc isn't an opcode we know, or has a parameter mode digit other than 0, 1 or 2
+    UnknownInstruction,
+    // an instruction tried to write its result to an immediate mode param
+    ImmediateModeWrite,
+    // a read, write or jump went to an address that's negative or too big to index memory with
+    InvalidAddress(i128),
+    // run_until_halt got to an input instruction with nothing left in the input queue
+    RanOutOfInput,
+    // the machine got as far as one of the limits it was given would let it, see intcode::limits
+    LimitExceeded(Limit),
+    // an add or mul whose answer doesn't fit in a cell, with Arithmetic::Checked
+    Overflow,
+    // the machine came back to exactly the same state without taking any input in between, so it'd
+    // go around forever. only checked for once detect_loops is on
+    InfiniteLoop { entry: usize, length: usize },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntcodeError {
+    pub kind: IntcodeErrorKind,
+    pub pc: usize,
+    pub raw_instruction: i128,
+    pub relative_base: i128,
+}
+
+impl fmt::Display for IntcodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let what = match self.kind {
+            IntcodeErrorKind::UnknownInstruction => "unknown instruction".to_owned(),
+            IntcodeErrorKind::ImmediateModeWrite => "write to an immediate mode param".to_owned(),
+            IntcodeErrorKind::InvalidAddress(addr) => format!("invalid address {}", addr),
+            IntcodeErrorKind::RanOutOfInput => "ran out of input".to_owned(),
+            IntcodeErrorKind::LimitExceeded(limit) => format!("hit the limit of {}", limit),
+            IntcodeErrorKind::Overflow => "overflow".to_owned(),
+            IntcodeErrorKind::InfiniteLoop { entry, length } => format!(
+                "stuck in a loop of {} instructions starting at {}",
+                length, entry
+            ),
+        };
+        write!(
+            f,
+            "{} at pc {} (instruction {}, relative base {})",
+            what, self.pc, self.raw_instruction, self.relative_base
+        )
+    }
+}
+
+impl std::error::Error for IntcodeError {}
@@ -0,0 +1,401 @@
+use crate::intcode::trace::NoTrace;
+use crate::intcode::{IntCodeComputer, RunResult};
+use std::collections::VecDeque;
+use std::fmt;
+
+// records a machine as it runs so it can be run backwards. every step pushes what it's about to
+// overwrite (pc, rb, the one memory cell it writes, whether it took an input) onto an undo log,
+// and every so often the whole machine gets cloned as a checkpoint. going back a few steps pops
+// the undo log, going back further restores the closest checkpoint before the target and runs
+// forward from there.
+//
+// the undo log is cleared at each checkpoint, so it never holds more than one checkpoint interval
+// of steps. only the last max_checkpoints checkpoints are kept: taking one more drops the oldest,
+// along with the inputs taken before the oldest one left, since replays never start before it.
+// that keeps memory bounded however long the machine runs, the price is that history only goes
+// back max_checkpoints * checkpoint_interval steps or so. going back further than that is an
+// error.
+//
+// only instructions that run count as steps: waiting for input, halting and errors don't. going
+// back doesn't lose anything, the inputs the machine took after that point go back on the front
+// of the input queue, so running forward again does the same thing it did the first time
+pub struct History {
+    icc: IntCodeComputer,
+    steps: usize,
+    undo: Vec<Undo>,
+    // oldest first
+    checkpoints: VecDeque<(usize, IntCodeComputer)>,
+    checkpoint_interval: usize,
+    next_checkpoint: usize,
+    max_checkpoints: usize,
+    // (step, value) for every input the machine took since the oldest checkpoint
+    inputs_taken: VecDeque<(usize, i128)>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum HistoryError {
+    // the step is from before the oldest checkpoint that's still kept, earliest is as far back as
+    // history goes now
+    Forgotten { earliest: usize },
+    // the machine halted, ran out of input or failed at this step, before it got where it was
+    // going
+    Stopped(usize),
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HistoryError::Forgotten { earliest } => write!(
+                f,
+                "that's too far back, history only goes back to step {}",
+                earliest
+            ),
+            HistoryError::Stopped(step) => write!(f, "the machine stopped at step {}", step),
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {}
+
+fn stopped(result: &Option<RunResult>) -> bool {
+    matches!(
+        result,
+        Some(RunResult::NeedMoreInput) | Some(RunResult::Halt) | Some(RunResult::Error(_))
+    )
+}
+
+struct Undo {
+    current_pos: usize,
+    relative_base: i128,
+    num_instructions_processed: usize,
+    write: Option<(usize, i128)>,
+    took_input: bool,
+}
+
+impl History {
+    pub fn new(icc: IntCodeComputer) -> Self {
+        Self::with_checkpoints(icc, 1000, 64)
+    }
+
+    pub fn with_checkpoints(
+        icc: IntCodeComputer,
+        checkpoint_interval: usize,
+        max_checkpoints: usize,
+    ) -> Self {
+        assert!(checkpoint_interval > 0 && max_checkpoints > 1);
+        Self {
+            icc,
+            steps: 0,
+            undo: vec![],
+            checkpoints: VecDeque::new(),
+            checkpoint_interval,
+            next_checkpoint: 0,
+            max_checkpoints,
+            inputs_taken: VecDeque::new(),
+        }
+    }
+
+    pub fn icc(&self) -> &IntCodeComputer {
+        &self.icc
+    }
+
+    // how many steps the machine has run since recording started
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    // the first step that can still be gone back to
+    pub fn earliest(&self) -> usize {
+        self.checkpoints.front().map_or(0, |(at, _)| *at)
+    }
+
+    pub fn queue_input(&mut self, input: i128) {
+        self.icc.queue_input(input);
+    }
+
+    // runs one instruction, None if it didn't produce anything
+    pub fn step(&mut self) -> Option<RunResult> {
+        if self.steps == self.next_checkpoint {
+            self.checkpoint();
+        }
+        let write = self
+            .icc
+            .next_write()
+            .map(|addr| (addr, self.icc.memory[addr]));
+        let undo = Undo {
+            current_pos: self.icc.current_pos,
+            relative_base: self.icc.relative_base,
+            num_instructions_processed: self.icc.num_instructions_processed,
+            write,
+            took_input: false,
+        };
+        let next_input = self.icc.input.back().cloned();
+        let queued = self.icc.input.len();
+
+        let result = self.icc.run_one_traced();
+        match result {
+            Some(RunResult::NeedMoreInput) | Some(RunResult::Halt) => {
+                self.icc.num_instructions_processed = undo.num_instructions_processed;
+                return result;
+            }
+            Some(RunResult::Error(_)) => return result,
+            Some(RunResult::Output(_)) | None => (),
+        }
+        let took_input = self.icc.input.len() < queued;
+        if took_input {
+            self.inputs_taken
+                .push_back((self.steps, next_input.unwrap()));
+        }
+        self.undo.push(Undo { took_input, ..undo });
+        self.steps += 1;
+        result
+    }
+
+    // same as IntCodeComputer::run_and_get_next, but recorded
+    pub fn run_and_get_next(&mut self) -> RunResult {
+        loop {
+            if let Some(result) = self.step() {
+                return result;
+            }
+        }
+    }
+
+    fn checkpoint(&mut self) {
+        self.checkpoints.push_back((self.steps, self.icc.clone()));
+        self.undo.clear();
+        self.next_checkpoint = self.steps + self.checkpoint_interval;
+        if self.checkpoints.len() > self.max_checkpoints {
+            self.checkpoints.pop_front();
+            let earliest = self.earliest();
+            while matches!(self.inputs_taken.front(), Some((step, _)) if *step < earliest) {
+                self.inputs_taken.pop_front();
+            }
+        }
+    }
+
+    // false if we're already as far back as history goes
+    pub fn step_back(&mut self) -> Result<bool, HistoryError> {
+        if self.steps == self.earliest() {
+            return Ok(false);
+        }
+        if self.undo.is_empty() {
+            self.replay_from_checkpoint(self.steps)?;
+        }
+        let undo = self.undo.pop().unwrap();
+        if let Some((addr, value)) = undo.write {
+            self.icc.write(addr, value, &mut NoTrace);
+        }
+        if undo.took_input {
+            let (_, input) = self.inputs_taken.pop_back().unwrap();
+            self.icc.input.push_back(input);
+        }
+        self.icc.current_pos = undo.current_pos;
+        self.icc.relative_base = undo.relative_base;
+        self.icc.num_instructions_processed = undo.num_instructions_processed;
+        self.steps -= 1;
+        Ok(true)
+    }
+
+    // goes back to the checkpoint before target and runs forward to it. afterwards the undo log
+    // goes back to that checkpoint. target can't be before the oldest checkpoint
+    fn replay_from_checkpoint(&mut self, target: usize) -> Result<(), HistoryError> {
+        let i = self
+            .checkpoints
+            .iter()
+            .rposition(|(at, _)| *at < target)
+            .unwrap_or(0);
+        let (at, checkpoint) = &self.checkpoints[i];
+        let taken_since = self.inputs_taken.iter().position(|(step, _)| step >= at);
+        let taken_since = match taken_since {
+            Some(j) => self.inputs_taken.split_off(j),
+            None => VecDeque::new(),
+        };
+        // the input queue is consumed from the back
+        let mut input = self.icc.input.clone();
+        input.extend(taken_since.into_iter().rev().map(|(_, value)| value));
+
+        self.steps = *at;
+        self.icc = checkpoint.clone();
+        self.icc.input = input;
+        self.checkpoints.truncate(i + 1);
+        self.next_checkpoint = self.steps + self.checkpoint_interval;
+        self.undo.clear();
+        self.run_to(target)
+    }
+
+    fn run_to(&mut self, target: usize) -> Result<(), HistoryError> {
+        while self.steps < target {
+            if stopped(&self.step()) {
+                return Err(HistoryError::Stopped(self.steps));
+            }
+        }
+        Ok(())
+    }
+
+    // runs forwards or backwards to just before the given step runs
+    pub fn goto(&mut self, target: usize) -> Result<(), HistoryError> {
+        let earliest = self.earliest();
+        if target < earliest {
+            return Err(HistoryError::Forgotten { earliest });
+        }
+        if target < self.steps.saturating_sub(self.undo.len()) {
+            self.replay_from_checkpoint(target)?;
+        }
+        while self.steps > target {
+            self.step_back()?;
+        }
+        self.run_to(target)
+    }
+
+    // steps back to right before the last instruction that wrote to addr and returns which step
+    // that was, or goes as far back as history goes and returns None if nothing did
+    pub fn reverse_continue_to_write(
+        &mut self,
+        addr: usize,
+    ) -> Result<Option<usize>, HistoryError> {
+        while self.steps > self.earliest() {
+            if self.undo.is_empty() {
+                self.replay_from_checkpoint(self.steps)?;
+            }
+            let wrote = self.undo.last().and_then(|undo| undo.write);
+            self.step_back()?;
+            if let Some((written, _)) = wrote {
+                if written == addr {
+                    return Ok(Some(self.steps));
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+fn day9_boost() -> IntCodeComputer {
+    use crate::intcode::parse_proggy;
+    IntCodeComputer::from_ints(parse_proggy(include_str!("../../input/2019/day9.txt")))
+}
+
+// undoing a write past the end of memory leaves memory longer than it was, so this compares
+// the cells rather than the snapshots
+#[cfg(test)]
+fn state(icc: &IntCodeComputer) -> (usize, i128, usize, Vec<i128>, Vec<i128>) {
+    (
+        icc.current_pos,
+        icc.relative_base,
+        icc.num_instructions_processed,
+        icc.input.iter().cloned().collect(),
+        (0..1100).map(|addr| icc.memory[addr]).collect(),
+    )
+}
+
+#[test]
+fn going_back_and_forth_ends_up_in_the_same_state() {
+    // boost in sensor boost mode runs a few hundred thousand steps, so this crosses plenty of
+    // checkpoints, but not so many that the first ones get dropped
+    let mut history = History::with_checkpoints(day9_boost(), 100, 64);
+    history.queue_input(2);
+    let mut states = vec![];
+    for _ in 0..5000 {
+        states.push(state(history.icc()));
+        history.step();
+    }
+
+    for &target in &[4999, 4321, 4000, 100, 3999, 0, 2500] {
+        assert_eq!(Ok(()), history.goto(target));
+        assert_eq!(states[target], state(history.icc()));
+    }
+    assert_eq!(Ok(()), history.goto(5000));
+    assert_eq!(Ok(true), history.step_back());
+    assert_eq!(states[4999], state(history.icc()));
+    assert_eq!(Ok(()), history.goto(0));
+    assert_eq!(Ok(false), history.step_back());
+
+    // and the machine still gets the right answer going forward
+    assert!(matches!(
+        history.run_and_get_next(),
+        RunResult::Output(35734)
+    ));
+}
+
+#[test]
+fn reverse_continues_to_the_last_write() {
+    let mut history = History::with_checkpoints(day9_boost(), 50, 64);
+    history.queue_input(1);
+    assert!(matches!(
+        history.run_and_get_next(),
+        RunResult::Output(3839402290)
+    ));
+    let end = history.steps();
+    let mut original = day9_boost();
+    original.queue_input(1);
+
+    // boost keeps its stack at [1000] and up, the input goes there first
+    let step = history.reverse_continue_to_write(1000).unwrap().unwrap();
+    assert_eq!(step, history.steps());
+    assert_eq!(Some(1000), history.icc.next_write());
+    for _ in 0..step {
+        original.run_one(&mut NoTrace);
+    }
+    assert_eq!(state(&original), state(history.icc()));
+
+    assert_eq!(Ok(()), history.goto(end));
+    assert_eq!(Ok(None), history.reverse_continue_to_write(123_456));
+    assert_eq!(0, history.steps());
+}
+
+#[test]
+fn memory_stays_bounded_on_long_runs() {
+    let mut history = History::with_checkpoints(day9_boost(), 1000, 8);
+    history.queue_input(2);
+    let mut most = (0, 0, 0);
+    loop {
+        let stored = (
+            history.undo.len(),
+            history.checkpoints.len(),
+            history.inputs_taken.len(),
+        );
+        most = (
+            most.0.max(stored.0),
+            most.1.max(stored.1),
+            most.2.max(stored.2),
+        );
+        match history.step() {
+            None => (),
+            Some(RunResult::Output(value)) => assert_eq!(35734, value),
+            Some(RunResult::Halt) => break,
+            Some(otherwise) => panic!("didn't expect {:?}", otherwise),
+        }
+    }
+    // the run is hundreds of times longer than the interval, but nothing grew with it. the one
+    // input is dropped along with the first checkpoint
+    assert!(history.steps() > 300_000);
+    assert_eq!((1000, 8, 1), most);
+    assert_eq!(0, history.inputs_taken.len());
+
+    // and going back stops at the oldest checkpoint kept
+    let earliest = history.earliest();
+    assert!(earliest > history.steps() - 9000);
+    assert_eq!(
+        Err(HistoryError::Forgotten { earliest }),
+        history.goto(earliest - 1)
+    );
+    assert_eq!(Ok(()), history.goto(earliest));
+    assert_eq!(Ok(false), history.step_back());
+    assert_eq!(Ok(None), history.reverse_continue_to_write(123_456));
+    assert_eq!(earliest, history.steps());
+}
+
+#[test]
+fn replays_that_stop_early_are_errors() {
+    let mut history = History::with_checkpoints(day9_boost(), 10, 64);
+    history.queue_input(1);
+    assert!(matches!(history.run_and_get_next(), RunResult::Output(_)));
+    // replaying past where the machine took its input needs that input again. without it the
+    // replay stops there
+    let (taken_at, _) = history.inputs_taken[0];
+    history.inputs_taken.clear();
+    assert_eq!(
+        Err(HistoryError::Stopped(taken_at)),
+        history.goto(taken_at + 1)
+    );
+}
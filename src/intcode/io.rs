@@ -0,0 +1,121 @@
+use crate::intcode::{IntCodeComputer, RunResult};
+use std::io::{BufRead, Write};
+use std::sync::mpsc::{Receiver, Sender};
+
+// where a machine gets its input from and sends its output to, so a driver can hand the whole
+// conversation over to run_with instead of queueing input every time the machine asks for it
+pub trait IoDevice {
+    // None when there's nothing to give the machine yet, run_with stops with NeedMoreInput and
+    // asks again next time it's called
+    fn input(&mut self) -> Option<i128>;
+    fn output(&mut self, value: i128);
+}
+
+// a pair of closures, the first one gives input and the second gets output
+impl<I: FnMut() -> Option<i128>, O: FnMut(i128)> IoDevice for (I, O) {
+    fn input(&mut self) -> Option<i128> {
+        (self.0)()
+    }
+
+    fn output(&mut self, value: i128) {
+        (self.1)(value)
+    }
+}
+
+// wires a machine up to channels, e.g. to another machine running on its own thread. waits for
+// input, and the machine stops once the sending side hangs up
+pub struct Channels {
+    pub input: Receiver<i128>,
+    pub output: Sender<i128>,
+}
+
+impl IoDevice for Channels {
+    fn input(&mut self) -> Option<i128> {
+        self.input.recv().ok()
+    }
+
+    // nobody's listening anymore, so there's nothing to do with it
+    fn output(&mut self, value: i128) {
+        self.output.send(value).ok();
+    }
+}
+
+// one number per line, e.g. reading from a file and writing to stdout
+pub struct Lines<R: BufRead, W: Write> {
+    pub input: R,
+    pub output: W,
+}
+
+impl<R: BufRead, W: Write> IoDevice for Lines<R, W> {
+    fn input(&mut self) -> Option<i128> {
+        let mut line = String::new();
+        match self.input.read_line(&mut line).unwrap() {
+            0 => None,
+            _ => Some(
+                line.trim()
+                    .parse()
+                    .unwrap_or_else(|_| panic!("expected a number but got {:?}", line)),
+            ),
+        }
+    }
+
+    fn output(&mut self, value: i128) {
+        writeln!(self.output, "{}", value).unwrap();
+    }
+}
+
+impl IntCodeComputer {
+    // runs until the machine halts, errors, or needs input the device doesn't have. input that
+    // was already queued gets used up before the device is asked for any. never gives back
+    // Output, that all goes to the device
+    pub fn run_with(&mut self, device: &mut impl IoDevice) -> RunResult {
+        loop {
+            match self.run_and_get_next() {
+                RunResult::Output(value) => device.output(value),
+                RunResult::NeedMoreInput => match device.input() {
+                    Some(input) => self.queue_input(input),
+                    None => return RunResult::NeedMoreInput,
+                },
+                result @ RunResult::Halt | result @ RunResult::Error(_) => return result,
+            }
+        }
+    }
+}
+
+#[test]
+fn runs_with_closures() {
+    // day9's example that outputs a copy of itself
+    let quine = vec![
+        109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ];
+    let mut output = vec![];
+    let mut icc = IntCodeComputer::from_ints(quine.clone());
+    let result = icc.run_with(&mut (|| None, |value| output.push(value)));
+    assert!(matches!(result, RunResult::Halt));
+    assert_eq!(quine, output);
+
+    // adds up pairs of numbers until it runs out
+    let adder = vec![3, 13, 3, 14, 1, 13, 14, 15, 4, 15, 1105, 1, 0, 0, 0, 0];
+    let mut inputs = vec![1, 2, 10, 20, 100].into_iter();
+    let mut output = vec![];
+    let mut icc = IntCodeComputer::from_ints(adder);
+    let result = icc.run_with(&mut (|| inputs.next(), |value| output.push(value)));
+    assert!(matches!(result, RunResult::NeedMoreInput));
+    assert_eq!(vec![3, 30], output);
+    let mut inputs = vec![200].into_iter();
+    let result = icc.run_with(&mut (|| inputs.next(), |value| output.push(value)));
+    assert!(matches!(result, RunResult::NeedMoreInput));
+    assert_eq!(vec![3, 30, 300], output);
+}
+
+#[test]
+fn runs_with_lines() {
+    let adder = vec![3, 13, 3, 14, 1, 13, 14, 15, 4, 15, 1105, 1, 0, 0, 0, 0];
+    let mut lines = Lines {
+        input: "1\n2\n-3\n4\n".as_bytes(),
+        output: vec![],
+    };
+    let result = IntCodeComputer::from_ints(adder).run_with(&mut lines);
+    assert!(matches!(result, RunResult::NeedMoreInput));
+    assert_eq!("3\n1\n", String::from_utf8(lines.output).unwrap());
+}
@@ -0,0 +1,146 @@
+use crate::intcode::IntCodeComputer;
+use std::fmt;
+
+// caps on how far a machine gets to go, so a program that's stuck in a loop or eating memory
+// stops instead of hanging. going over one is an IntcodeErrorKind::LimitExceeded error, and like
+// any other error it leaves the machine on the instruction that would have gone over, so raising
+// the limit and running again picks up from there
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Limits {
+    // instructions run over the machine's whole life, the same count as num_instructions_processed
+    pub steps: usize,
+    // cells memory keeps, Memory::cells(). that's not how many the program has written: a write
+    // a little past the end of the program grows it over everything in between, so one write can
+    // store thousands of cells
+    pub stored_cells: usize,
+    // the highest address that can be read, written or run
+    pub address: usize,
+}
+
+impl Limits {
+    pub fn none() -> Self {
+        Self {
+            steps: usize::MAX,
+            stored_cells: usize::MAX,
+            address: usize::MAX,
+        }
+    }
+
+    pub fn steps(mut self, steps: usize) -> Self {
+        self.steps = steps;
+        self
+    }
+
+    pub fn stored_cells(mut self, stored_cells: usize) -> Self {
+        self.stored_cells = stored_cells;
+        self
+    }
+
+    pub fn address(mut self, address: usize) -> Self {
+        self.address = address;
+        self
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+// which limit was hit, and what it was set to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Limit {
+    Steps(usize),
+    StoredCells(usize),
+    Address(usize),
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Limit::Steps(steps) => write!(f, "{} steps", steps),
+            Limit::StoredCells(cells) => write!(f, "{} stored memory cells", cells),
+            Limit::Address(address) => write!(f, "address {}", address),
+        }
+    }
+}
+
+impl IntCodeComputer {
+    pub fn limits(&self) -> Limits {
+        self.limits
+    }
+
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.set_limits(limits);
+        self
+    }
+}
+
+#[test]
+fn limits_stop_runaway_programs() {
+    use crate::intcode::{IntcodeErrorKind, RunResult};
+
+    let kind = |result| match result {
+        RunResult::Error(e) => e.kind,
+        otherwise => panic!("expected an error, but got {:?}", otherwise),
+    };
+
+    // prints 1 forever
+    let mut icc =
+        IntCodeComputer::from_ints(vec![104, 1, 1105, 1, 0]).with_limits(Limits::none().steps(5));
+    let (output, result) = icc.run_and_collect_all_output();
+    assert_eq!(vec![1, 1, 1], output);
+    assert_eq!(
+        IntcodeErrorKind::LimitExceeded(Limit::Steps(5)),
+        kind(result)
+    );
+    // picks up where it stopped once there's more to spend
+    icc.set_limits(Limits::none().steps(6));
+    let (output, result) = icc.run_and_collect_all_output();
+    assert!(output.is_empty());
+    assert_eq!(
+        IntcodeErrorKind::LimitExceeded(Limit::Steps(6)),
+        kind(result)
+    );
+    assert_eq!(6, icc.num_instructions_processed);
+
+    // waiting for input doesn't use any up
+    let mut icc = IntCodeComputer::from_ints(vec![3, 0, 99]).with_limits(Limits::none().steps(2));
+    for _ in 0..5 {
+        assert!(matches!(icc.run_and_get_next(), RunResult::NeedMoreInput));
+    }
+    assert_eq!(0, icc.num_instructions_processed);
+    icc.queue_input(1);
+    assert!(icc.try_run_until_halt().is_ok());
+
+    // writes a cell further along every time around
+    let proggy = vec![1101, 0, 0, 1000, 1001, 3, 1, 3, 1105, 1, 0];
+    let mut icc =
+        IntCodeComputer::from_ints(proggy.clone()).with_limits(Limits::none().stored_cells(1003));
+    let (_, result) = icc.run_and_collect_all_output();
+    assert_eq!(
+        IntcodeErrorKind::LimitExceeded(Limit::StoredCells(1003)),
+        kind(result)
+    );
+    assert_eq!(1003, icc.memory.cells());
+    let mut icc = IntCodeComputer::from_ints(proggy).with_limits(Limits::none().address(5000));
+    let (_, result) = icc.run_and_collect_all_output();
+    assert_eq!(
+        IntcodeErrorKind::LimitExceeded(Limit::Address(5000)),
+        kind(result)
+    );
+    assert_eq!(5001, icc.memory.cells());
+
+    // runs off into memory that's never been written
+    let mut icc =
+        IntCodeComputer::from_ints(vec![1105, 1, 100]).with_limits(Limits::none().address(99));
+    assert_eq!(
+        IntcodeErrorKind::LimitExceeded(Limit::Address(99)),
+        icc.try_run_until_halt().unwrap_err().kind
+    );
+}
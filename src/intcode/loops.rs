@@ -0,0 +1,215 @@
+use crate::intcode::limits::Limits;
+use crate::intcode::trace::NoTrace;
+use crate::intcode::{IntCodeComputer, IntcodeErrorKind, Memory};
+use std::collections::HashMap;
+
+// proves a machine is stuck: if it comes back around to exactly the same pc, relative base and
+// memory without taking any input in between, it's going to keep doing that forever. output
+// doesn't change anything the machine does next, so a loop that prints (like day25's infinite
+// loop item, which says you took it over and over) is just as stuck as one that doesn't.
+//
+// it's brent's cycle finding. every so often (at steps that are powers of two since the last
+// checkpoint) the current state becomes the checkpoint, and every step after that gets compared
+// against it. memory isn't copied for that, the detector just remembers what every cell written
+// since the checkpoint held at the time, so a comparison only looks at those cells
+#[derive(Clone, Debug, Default)]
+pub struct LoopDetector {
+    checkpoint_pc: usize,
+    checkpoint_rb: i128,
+    since_checkpoint: HashMap<usize, i128>,
+    // steps since the checkpoint, and how many there'll be before it moves up
+    steps: usize,
+    power: usize,
+    // the same for the last input, for working out where the loop starts once it's found
+    input_pc: usize,
+    input_rb: i128,
+    since_input: HashMap<usize, i128>,
+    // the machine took input (or stopped to wait for it), so the states before don't count
+    input: bool,
+}
+
+impl LoopDetector {
+    fn reset(&mut self, pc: usize, rb: i128) {
+        self.checkpoint_pc = pc;
+        self.checkpoint_rb = rb;
+        self.since_checkpoint.clear();
+        self.steps = 0;
+        self.power = 1;
+        self.input_pc = pc;
+        self.input_rb = rb;
+        self.since_input.clear();
+        self.input = false;
+    }
+
+    // called with what's in a cell right before it gets written over
+    pub fn write(&mut self, addr: usize, old_value: i128) {
+        self.since_checkpoint.entry(addr).or_insert(old_value);
+        self.since_input.entry(addr).or_insert(old_value);
+    }
+
+    pub fn input(&mut self) {
+        self.input = true;
+    }
+
+    // the machine ran an instruction that didn't take any input
+    pub fn stepped(&mut self) {
+        self.steps += 1;
+    }
+
+    // checked before every step. if the machine's in a loop, gives back where the loop starts and
+    // how many instructions go around it. it doesn't move on until the machine does, so checking
+    // again finds the same loop
+    pub fn check(&mut self, icc: &IntCodeComputer) -> Option<IntcodeErrorKind> {
+        let (pc, rb) = (icc.current_pos, icc.relative_base);
+        if self.input {
+            self.reset(pc, rb);
+            return None;
+        }
+        if self.steps > 0
+            && pc == self.checkpoint_pc
+            && rb == self.checkpoint_rb
+            && same_cells(&self.since_checkpoint, &icc.memory, &icc.memory)
+        {
+            return Some(IntcodeErrorKind::InfiniteLoop {
+                entry: self.entry(icc, self.steps),
+                length: self.steps,
+            });
+        }
+        if self.steps == self.power {
+            self.checkpoint_pc = pc;
+            self.checkpoint_rb = rb;
+            self.since_checkpoint.clear();
+            self.power *= 2;
+            self.steps = 0;
+        }
+        None
+    }
+
+    // the pc of the first state that repeats. the machine gets wound back to the last input, then
+    // one copy runs length steps ahead of another and they go in lockstep until they're the same
+    fn entry(&self, icc: &IntCodeComputer, length: usize) -> usize {
+        let mut behind = icc.clone();
+        behind.loop_detector = None;
+        behind.limits = Limits::none();
+        behind.tracer = None;
+        behind.current_pos = self.input_pc;
+        behind.relative_base = self.input_rb;
+        for (&addr, &value) in &self.since_input {
+            behind.memory[addr] = value;
+            behind.decode_cache.invalidate(addr);
+        }
+        let mut ahead = behind.clone();
+        // whatever gets printed on the way around is thrown away
+        let step = |icc: &mut IntCodeComputer| {
+            icc.run_one(&mut NoTrace);
+        };
+        for _ in 0..length {
+            step(&mut ahead);
+        }
+        while behind.current_pos != ahead.current_pos
+            || behind.relative_base != ahead.relative_base
+            || !same_cells(&self.since_input, &behind.memory, &ahead.memory)
+        {
+            step(&mut behind);
+            step(&mut ahead);
+        }
+        behind.current_pos
+    }
+}
+
+// whether a and b agree on the cells that could have changed, which are the keys of the map. the
+// values are what they were when it started, so passing the same memory twice compares it against
+// that instead
+fn same_cells(changed: &HashMap<usize, i128>, a: &Memory, b: &Memory) -> bool {
+    if std::ptr::eq(a, b) {
+        changed.iter().all(|(&addr, &value)| a[addr] == value)
+    } else {
+        changed.keys().all(|&addr| a[addr] == b[addr])
+    }
+}
+
+impl IntCodeComputer {
+    // from now on, running into a loop the machine can never get out of is an
+    // IntcodeErrorKind::InfiniteLoop error instead of running forever
+    pub fn detect_loops(&mut self) {
+        let mut detector = LoopDetector::default();
+        detector.reset(self.current_pos, self.relative_base);
+        self.loop_detector = Some(detector);
+    }
+}
+
+#[test]
+fn finds_loops_but_not_slow_programs() {
+    use crate::intcode::assemble::assemble;
+    use crate::intcode::{IntcodeError, RunResult};
+
+    let stuck = |proggy: &str| {
+        let mut icc = IntCodeComputer::from_ints(assemble(proggy).unwrap());
+        icc.detect_loops();
+        icc.try_run_until_halt().map_err(|e| e.kind)
+    };
+
+    // counts to 3 and prints it, then gets stuck flipping a cell back and forth
+    let flips = "
+        start: add [n], 1, [n]
+        lt [n], 3, [more]
+        jnz [more], start
+        out [n]
+        flip: eq [x], 0, [x]
+        jmp flip
+        n: data 0
+        more: data 0
+        x: data 0
+        ";
+    assert_eq!(
+        Err(IntcodeErrorKind::InfiniteLoop {
+            entry: 13,
+            length: 4,
+        }),
+        stuck(flips)
+    );
+
+    // counting up forever never repeats, so it's slow rather than stuck. it takes a step limit to
+    // stop it
+    let mut icc = IntCodeComputer::from_ints(
+        assemble(
+            "
+            loop: add [n], 1, [n]
+            jmp loop
+            n: data 0
+            ",
+        )
+        .unwrap(),
+    )
+    .with_limits(Limits::none().steps(100_000));
+    icc.detect_loops();
+    assert!(matches!(
+        icc.try_run_until_halt().unwrap_err().kind,
+        IntcodeErrorKind::LimitExceeded(_)
+    ));
+
+    // printing the same thing forever is stuck, but what it printed before it was caught still
+    // comes out
+    let mut icc = IntCodeComputer::from_ints(vec![104, 1, 1105, 1, 0]);
+    icc.detect_loops();
+    let (output, result) = icc.run_and_collect_all_output();
+    assert_eq!(vec![1, 1], output);
+    assert!(matches!(
+        result,
+        RunResult::Error(IntcodeError {
+            kind: IntcodeErrorKind::InfiniteLoop {
+                entry: 0,
+                length: 2
+            },
+            ..
+        })
+    ));
+
+    // but reading input every time around isn't
+    let mut icc = IntCodeComputer::from_ints(vec![3, 100, 1105, 1, 0]);
+    icc.detect_loops();
+    for _ in 0..10 {
+        icc.queue_input(5);
+    }
+    assert!(matches!(icc.run_and_get_next(), RunResult::NeedMoreInput));
+}
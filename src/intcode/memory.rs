@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::sync::Arc;
+
+// writes this close past the end of the dense part just grow it, anything further out goes into
+// the sparse map so a single write to address 1_000_000_000 doesn't allocate gigabytes
+const DENSE_GROWTH_LIMIT: usize = 4096;
+
+const PAGE_SIZE: usize = 256;
+
+#[derive(Clone)]
+struct Page([i128; PAGE_SIZE]);
+
+// arrays this long don't have a Debug of their own
+impl fmt::Debug for Page {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0[..].fmt(f)
+    }
+}
+
+// intcode memory: the program image lives in pages, far away addresses in a HashMap. reads of
+// cells that were never written return 0, same as the old DefaultHashMap<usize, String>.
+//
+// it's copy on write, so cloning a machine (day15 clones one for every step it tries, day19 one
+// for every point in the beam) copies no cells at all, just bumps a count on every page. a page
+// that's shared gets copied the first time either side writes to it, and from then on it's that
+// side's own again
+#[derive(Clone, Debug, Default)]
+pub struct Memory {
+    pages: Vec<Arc<Page>>,
+    // the last page is only used up to here, the rest of it is zeroes
+    dense_len: usize,
+    sparse: Arc<HashMap<usize, i128>>,
+}
+
+impl Memory {
+    pub fn new(proggy: Vec<i128>) -> Self {
+        let pages = proggy
+            .chunks(PAGE_SIZE)
+            .map(|chunk| {
+                let mut cells = [0; PAGE_SIZE];
+                cells[..chunk.len()].copy_from_slice(chunk);
+                Arc::new(Page(cells))
+            })
+            .collect();
+        Self {
+            pages,
+            dense_len: proggy.len(),
+            sparse: Arc::default(),
+        }
+    }
+
+    // memory laid out exactly as it's given, for putting back one that was saved. the sparse cells
+    // stay sparse even if they're close enough to the end of the dense part that writing them would
+    // have grown it
+    pub fn from_parts(dense: Vec<i128>, sparse: HashMap<usize, i128>) -> Self {
+        Self {
+            sparse: Arc::new(sparse),
+            ..Self::new(dense)
+        }
+    }
+
+    // how much of memory is stored contiguously, starts out as the length of the program
+    pub fn dense_len(&self) -> usize {
+        self.dense_len
+    }
+
+    // every cell that's stored, dense or sparse
+    pub fn cells(&self) -> usize {
+        self.dense_len + self.sparse.len()
+    }
+
+    // what cells would be after a write to addr
+    pub fn cells_after_write(&self, addr: usize) -> usize {
+        if addr < self.dense_len || self.sparse.contains_key(&addr) {
+            self.cells()
+        } else if addr - self.dense_len < DENSE_GROWTH_LIMIT {
+            // sparse cells the dense part grows over get moved into it
+            let moved = self.sparse.keys().filter(|&&sparse| sparse < addr).count();
+            addr + 1 + self.sparse.len() - moved
+        } else {
+            self.cells() + 1
+        }
+    }
+
+    pub fn get(&self, addr: usize) -> i128 {
+        self[addr]
+    }
+
+    pub fn set(&mut self, addr: usize, value: i128) {
+        self[addr] = value;
+    }
+
+    // the three cells after addr, which is what an instruction's operands are. it's read for every
+    // instruction that runs, and they're nearly always on the same page
+    #[inline(always)]
+    pub fn operands(&self, addr: usize) -> [i128; 3] {
+        let offset = addr % PAGE_SIZE;
+        if offset + 3 < PAGE_SIZE && addr + 3 < self.dense_len {
+            let cells = &self.pages[addr / PAGE_SIZE].0;
+            [cells[offset + 1], cells[offset + 2], cells[offset + 3]]
+        } else {
+            // there's nothing past the top of memory
+            let cell = |i| addr.checked_add(i).map_or(0, |addr| self[addr]);
+            [cell(1), cell(2), cell(3)]
+        }
+    }
+
+    pub fn dense(&self) -> impl Iterator<Item = i128> + '_ {
+        self.pages
+            .iter()
+            .flat_map(|page| page.0.iter())
+            .take(self.dense_len)
+            .copied()
+    }
+
+    // the cells that were written too far past the end of dense memory to be kept in it
+    pub fn sparse(&self) -> &HashMap<usize, i128> {
+        &self.sparse
+    }
+
+    // whether other copies of this memory are still looking at the same page as this one
+    #[cfg(test)]
+    fn shares_page_with(&self, other: &Memory, addr: usize) -> bool {
+        match (
+            self.pages.get(addr / PAGE_SIZE),
+            other.pages.get(addr / PAGE_SIZE),
+        ) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
+    fn grow_dense_to(&mut self, new_len: usize) {
+        let old_len = self.dense_len;
+        let pages = &mut self.pages;
+        while pages.len() * PAGE_SIZE < new_len {
+            pages.push(Arc::new(Page([0; PAGE_SIZE])));
+        }
+        self.dense_len = new_len;
+        if self.sparse.is_empty() {
+            return;
+        }
+        let moved = self
+            .sparse
+            .keys()
+            .filter(|addr| (old_len..new_len).contains(*addr))
+            .cloned()
+            .collect::<Vec<_>>();
+        for addr in moved {
+            let value = Arc::make_mut(&mut self.sparse).remove(&addr).unwrap();
+            self[addr] = value;
+        }
+    }
+}
+
+impl Index<usize> for Memory {
+    type Output = i128;
+
+    fn index(&self, addr: usize) -> &i128 {
+        if addr < self.dense_len {
+            &self.pages[addr / PAGE_SIZE].0[addr % PAGE_SIZE]
+        } else {
+            self.sparse.get(&addr).unwrap_or(&0)
+        }
+    }
+}
+
+impl IndexMut<usize> for Memory {
+    fn index_mut(&mut self, addr: usize) -> &mut i128 {
+        if addr >= self.dense_len {
+            if addr - self.dense_len < DENSE_GROWTH_LIMIT {
+                self.grow_dense_to(addr + 1);
+            } else {
+                return Arc::make_mut(&mut self.sparse).entry(addr).or_insert(0);
+            }
+        }
+        &mut Arc::make_mut(&mut self.pages[addr / PAGE_SIZE]).0[addr % PAGE_SIZE]
+    }
+}
+
+#[test]
+fn reads_past_the_end_are_zero() {
+    let memory = Memory::new(vec![1, 2, 3]);
+    assert_eq!(3, memory[2]);
+    assert_eq!(0, memory[3]);
+    assert_eq!(0, memory[1_000_000_000]);
+}
+
+#[test]
+fn writes_past_the_end_grow_memory() {
+    let mut memory = Memory::new(vec![1, 2, 3]);
+    memory[1_000_000] = 7;
+    memory[10] = 5;
+    assert_eq!(7, memory[1_000_000]);
+    assert_eq!(5, memory[10]);
+    assert_eq!(0, memory[9]);
+    assert_eq!(3, memory[2]);
+}
+
+#[test]
+fn clones_only_copy_the_pages_they_write_to() {
+    let mut memory = Memory::new((0..1000).collect());
+    memory[100_000] = 7;
+    let mut clone = memory.clone();
+    assert!(memory.shares_page_with(&clone, 0));
+    assert!(memory.shares_page_with(&clone, 999));
+
+    clone[999] = -1;
+    clone[100_000] = 8;
+    assert_eq!((999, -1), (memory[999], clone[999]));
+    assert_eq!((7, 8), (memory[100_000], clone[100_000]));
+    assert!(memory.shares_page_with(&clone, 0));
+    assert!(!memory.shares_page_with(&clone, 999));
+
+    // a page that was copied is shared again by the next clone
+    let again = clone.clone();
+    assert!(clone.shares_page_with(&again, 999));
+}
@@ -0,0 +1,323 @@
+use crate::intcode::{IntCodeComputer, IntcodeError, RunResult};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+
+// machines wired up to each other, declared up front and then run on one thread. nodes get
+// addresses in the order they're added, starting from 0, and monitors sit at whatever address
+// they're given. every round each node runs until it's stuck waiting for input (or halted),
+// sending what it outputs wherever its route says. a round where nothing gets sent means the
+// network's idle, and the monitors get a chance to do something about it. packets for an address
+// that's negative or has nothing at it are dropped, and a node that fails stops the whole network
+
+// where a node's output goes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Route {
+    // every value goes straight to the node or monitor at that address
+    Pipe(usize),
+    // outputs come in threes, (dest, x, y), and x and y get sent to dest
+    Packets,
+    // it's thrown away
+    Nowhere,
+}
+
+pub struct Node {
+    icc: IntCodeComputer,
+    route: Route,
+    // given to the node when it wants input and there's none waiting, once a round
+    when_empty: Option<i128>,
+    unsent: Vec<i128>,
+}
+
+impl Node {
+    pub fn new(icc: IntCodeComputer) -> Self {
+        Self {
+            icc,
+            route: Route::Nowhere,
+            when_empty: None,
+            unsent: vec![],
+        }
+    }
+
+    // input the node starts with, like a phase setting or its address
+    pub fn input(mut self, inputs: &[i128]) -> Self {
+        for &input in inputs {
+            self.icc.queue_input(input);
+        }
+        self
+    }
+
+    pub fn route(mut self, route: Route) -> Self {
+        self.route = route;
+        self
+    }
+
+    pub fn when_empty(mut self, input: i128) -> Self {
+        self.when_empty = Some(input);
+        self
+    }
+}
+
+// what a monitor wants done after it's been told something
+#[derive(Debug, PartialEq)]
+pub enum Action {
+    Nothing,
+    Send(usize, Vec<i128>),
+    // stops the whole network, run gives back the value
+    Stop(i128),
+}
+
+// a node that isn't a machine, for watching what goes by and stepping in, like day23's nat
+pub trait Monitor {
+    // a value that came down a pipe, or the x and y of a packet
+    fn receive(&mut self, message: &[i128]) -> Action;
+
+    // nothing was sent the whole last round. if every monitor does nothing the network stops
+    fn idle(&mut self) -> Action {
+        Action::Nothing
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct NetworkError {
+    pub node: usize,
+    pub error: IntcodeError,
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "node {} failed: {}", self.node, self.error)
+    }
+}
+
+impl std::error::Error for NetworkError {}
+
+// how a node's turn ended
+enum Turn {
+    // it's waiting or halted, and this is whether it sent anything
+    Done(bool),
+    // a monitor stopped the network with this value
+    Stopped(i128),
+}
+
+#[derive(Default)]
+pub struct Network {
+    nodes: Vec<Node>,
+    monitors: BTreeMap<usize, Box<dyn Monitor>>,
+}
+
+impl Network {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn node(mut self, node: Node) -> Self {
+        self.nodes.push(node);
+        self
+    }
+
+    pub fn monitor(mut self, addr: usize, monitor: impl Monitor + 'static) -> Self {
+        self.monitors.insert(addr, Box::new(monitor));
+        self
+    }
+
+    // Some if a monitor stopped the network
+    fn send(&mut self, dest: usize, message: &[i128]) -> Option<i128> {
+        if let Some(node) = self.nodes.get_mut(dest) {
+            for &value in message {
+                node.icc.queue_input(value);
+            }
+            return None;
+        }
+        let action = match self.monitors.get_mut(&dest) {
+            Some(monitor) => monitor.receive(message),
+            None => Action::Nothing,
+        };
+        self.act(action)
+    }
+
+    fn act(&mut self, action: Action) -> Option<i128> {
+        match action {
+            Action::Nothing => None,
+            Action::Send(dest, message) => self.send(dest, &message),
+            Action::Stop(value) => Some(value),
+        }
+    }
+
+    // runs a node until it's waiting or halted
+    fn run_node(&mut self, addr: usize) -> Result<Turn, NetworkError> {
+        let mut sent = false;
+        let mut gave_when_empty = false;
+        loop {
+            let node = &mut self.nodes[addr];
+            match node.icc.run_and_get_next() {
+                RunResult::Output(value) => node.unsent.push(value),
+                RunResult::NeedMoreInput => match node.when_empty {
+                    Some(input) if !gave_when_empty => {
+                        gave_when_empty = true;
+                        node.icc.queue_input(input);
+                    }
+                    _ => return Ok(Turn::Done(sent)),
+                },
+                RunResult::Halt => return Ok(Turn::Done(sent)),
+                RunResult::Error(error) => return Err(NetworkError { node: addr, error }),
+            }
+
+            let node = &mut self.nodes[addr];
+            let (dest, message) = match (node.route, &node.unsent[..]) {
+                (Route::Pipe(dest), [value]) => (Some(dest), vec![*value]),
+                (Route::Packets, [dest, x, y]) => (usize::try_from(*dest).ok(), vec![*x, *y]),
+                (Route::Nowhere, _) => {
+                    node.unsent.clear();
+                    continue;
+                }
+                _ => continue,
+            };
+            node.unsent.clear();
+            sent = true;
+            if let Some(value) = dest.and_then(|dest| self.send(dest, &message)) {
+                return Ok(Turn::Stopped(value));
+            }
+        }
+    }
+
+    // runs until a monitor stops it, or until it's idle and none of the monitors do anything,
+    // which gives back None
+    pub fn run(mut self) -> Result<Option<i128>, NetworkError> {
+        loop {
+            let mut sent = false;
+            for addr in 0..self.nodes.len() {
+                match self.run_node(addr)? {
+                    Turn::Done(sent_anything) => sent |= sent_anything,
+                    Turn::Stopped(value) => return Ok(Some(value)),
+                }
+            }
+            if sent {
+                continue;
+            }
+            let actions = self
+                .monitors
+                .values_mut()
+                .map(|monitor| monitor.idle())
+                .collect::<Vec<_>>();
+            if actions.iter().all(|action| *action == Action::Nothing) {
+                return Ok(None);
+            }
+            for action in actions {
+                if let Some(value) = self.act(action) {
+                    return Ok(Some(value));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+struct Last(Option<i128>);
+
+#[cfg(test)]
+impl Monitor for Last {
+    fn receive(&mut self, message: &[i128]) -> Action {
+        self.0 = Some(message[0]);
+        Action::Nothing
+    }
+
+    fn idle(&mut self) -> Action {
+        Action::Stop(self.0.unwrap())
+    }
+}
+
+#[test]
+fn pipes_and_packets() {
+    use crate::intcode::assemble::assemble;
+
+    // doubles whatever it gets
+    let double = assemble(
+        "
+        loop: in [x]
+        mul [x], 2, [x]
+        out [x]
+        jmp loop
+        x: data 0
+        ",
+    )
+    .unwrap();
+    let double = || IntCodeComputer::from_ints(double.clone());
+    let chain = Network::new()
+        .node(Node::new(double()).input(&[3]).route(Route::Pipe(1)))
+        .node(Node::new(double()).route(Route::Pipe(2)))
+        .node(Node::new(double()).route(Route::Pipe(100)))
+        .monitor(100, Last(None));
+    assert_eq!(Ok(Some(24)), chain.run());
+
+    // sends itself a packet, then passes along whatever it's sent to 100
+    let relay = assemble(
+        "
+        in [addr]
+        out [addr]
+        out [addr]
+        out 10
+        loop: in [x]
+        eq [x], -1, [empty]
+        jnz [empty], loop
+        in [y]
+        out 100
+        out [x]
+        out [y]
+        jmp loop
+        addr: data 0
+        x: data 0
+        y: data 0
+        empty: data 0
+        ",
+    )
+    .unwrap();
+    let relay = |addr| {
+        Node::new(IntCodeComputer::from_ints(relay.clone()))
+            .input(&[addr])
+            .route(Route::Packets)
+            .when_empty(-1)
+    };
+    let star = Network::new()
+        .node(relay(0))
+        .node(relay(1))
+        .monitor(100, Last(None));
+    // 1's packet is the last thing sent before it all goes quiet
+    assert_eq!(Ok(Some(1)), star.run());
+}
+
+#[test]
+fn bad_packets_and_bad_nodes_dont_take_the_network_down() {
+    use crate::intcode::assemble::assemble;
+    use crate::intcode::IntcodeErrorKind;
+
+    // sends packets to an address that's negative and one with nothing at it, and then one to 100
+    let scattered = assemble(
+        "
+        out -5
+        out 1
+        out 1
+        out 7
+        out 2
+        out 2
+        out 100
+        out 3
+        out 3
+        halt
+        ",
+    )
+    .unwrap();
+    let network = Network::new()
+        .node(Node::new(IntCodeComputer::from_ints(scattered)).route(Route::Packets))
+        .monitor(100, Last(None));
+    assert_eq!(Ok(Some(3)), network.run());
+
+    // node 1 goes wrong on its first instruction
+    let network = Network::new()
+        .node(Node::new(IntCodeComputer::from_ints(vec![104, 1, 99])).route(Route::Pipe(1)))
+        .node(Node::new(IntCodeComputer::from_ints(vec![3, 10, 42])))
+        .monitor(100, Last(None));
+    let e = network.run().unwrap_err();
+    assert_eq!(1, e.node);
+    assert_eq!(IntcodeErrorKind::UnknownInstruction, e.error.kind);
+}
@@ -0,0 +1,267 @@
+use crate::intcode::disassemble::label;
+use crate::intcode::trace::Tracer;
+use crate::intcode::Instruction::{JumpIfFalse6, JumpIfTrue5};
+use crate::intcode::ParameterMode::{ImmediateMode1, PositionMode0, RelativeMode2};
+use crate::intcode::{Instruction, IntCodeComputer};
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+// calls get deeper than this when the return address guessing goes wrong, so stop there rather
+// than growing the stack forever
+const MAX_STACK_DEPTH: usize = 256;
+
+// counts what a machine spends its time on. it's a tracer, so it sees every instruction:
+//
+// - how many times each address ran, and how many times each instruction (opcode and modes) ran
+// - basic blocks as they actually ran: a block starts wherever execution didn't just fall through
+//   from the previous instruction, or right after a jump
+// - loop back edges, jumps that went backwards, and how often they were taken
+// - a guess at the call stack for collapsed stacks. intcode has no call instruction, so a jump is
+//   a call when the address right after it was written somewhere in the same block (that's the
+//   return address being pushed), and a jump to a return address on the stack returns to it
+#[derive(Default)]
+pub struct Profiler {
+    total: u64,
+    addresses: HashMap<usize, (Instruction, u64)>,
+    instructions: HashMap<Instruction, u64>,
+    // block start -> (times entered, instructions run in it)
+    blocks: HashMap<usize, (u64, u64)>,
+    back_edges: HashMap<(usize, usize), u64>,
+    stacks: HashMap<Vec<usize>, u64>,
+
+    previous: Option<(usize, Instruction)>,
+    block: usize,
+    written_in_block: Vec<i128>,
+    // entry addresses of the functions we're in, and where each of them returns to
+    stack: Vec<usize>,
+    returns: Vec<usize>,
+    // an input instruction that had to wait gets begun again once there's input, but it's only
+    // counted the first time
+    waiting: bool,
+}
+
+fn describe(instruction: &Instruction) -> String {
+    let modes = instruction.modes().into_iter().map(|mode| match mode {
+        PositionMode0 => "pos",
+        ImmediateMode1 => "imm",
+        RelativeMode2 => "rel",
+    });
+    format!("{:<4} {}", instruction.mnemonic(), modes.format(", "))
+        .trim_end()
+        .to_owned()
+}
+
+fn percent(count: u64, total: u64) -> f64 {
+    100.0 * count as f64 / total.max(1) as f64
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    pub fn address_count(&self, addr: usize) -> u64 {
+        self.addresses.get(&addr).map_or(0, |(_, count)| *count)
+    }
+
+    fn enter_block(&mut self, pc: usize) {
+        self.block = pc;
+        self.written_in_block.clear();
+        self.blocks.entry(pc).or_insert((0, 0)).0 += 1;
+    }
+
+    // the hottest `top` of everything, most expensive first
+    pub fn report(&self, top: usize) -> String {
+        let mut lines = vec![format!("{} instructions", self.total)];
+
+        lines.push(String::new());
+        lines.push("hottest addresses:".to_owned());
+        for (addr, (instruction, count)) in self
+            .addresses
+            .iter()
+            .sorted_by_key(|(addr, (_, count))| (std::cmp::Reverse(*count), **addr))
+            .take(top)
+        {
+            lines.push(format!(
+                "{:>12} {:>6.2}% {:>6}: {}",
+                count,
+                percent(*count, self.total),
+                addr,
+                describe(instruction)
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push("instructions:".to_owned());
+        for (instruction, count) in self
+            .instructions
+            .iter()
+            .sorted_by_key(|(instruction, count)| {
+                (std::cmp::Reverse(**count), describe(instruction))
+            })
+            .take(top)
+        {
+            lines.push(format!(
+                "{:>12} {:>6.2}% {}",
+                count,
+                percent(*count, self.total),
+                describe(instruction)
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push("hot blocks:".to_owned());
+        for (start, (entered, count)) in self
+            .blocks
+            .iter()
+            .sorted_by_key(|(start, (_, count))| (std::cmp::Reverse(*count), **start))
+            .take(top)
+        {
+            lines.push(format!(
+                "{:>12} {:>6.2}% {:>6}: entered {} times",
+                count,
+                percent(*count, self.total),
+                label(*start),
+                entered
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push("loop back edges:".to_owned());
+        for ((from, to), count) in self
+            .back_edges
+            .iter()
+            .sorted_by_key(|((from, to), count)| (std::cmp::Reverse(**count), *from, *to))
+            .take(top)
+        {
+            lines.push(format!("{:>12} {:>6} -> {}", count, from, label(*to)));
+        }
+        lines.join("\n")
+    }
+
+    // one line per call stack with how many instructions ran in it, e.g. `L0;L922;L922 1234`,
+    // which is what flamegraph.pl and friends read
+    pub fn collapsed_stacks(&self) -> String {
+        self.stacks
+            .iter()
+            .map(|(stack, count)| {
+                format!("{} {}", stack.iter().map(|a| label(*a)).join(";"), count)
+            })
+            .sorted()
+            .join("\n")
+    }
+}
+
+impl Tracer for Profiler {
+    fn begin(&mut self, _step: usize, pc: usize, instruction: Option<Instruction>) {
+        let instruction = match instruction {
+            Some(instruction) => instruction,
+            None => return,
+        };
+        if self.waiting {
+            self.waiting = false;
+            if self.previous.map(|(previous_pc, _)| previous_pc) == Some(pc) {
+                return;
+            }
+        }
+        match self.previous {
+            None => {
+                self.stack = vec![pc];
+                self.enter_block(pc);
+            }
+            Some((previous_pc, previous)) => {
+                let next = previous_pc + previous.size();
+                let jumped = pc != next;
+                if jumped {
+                    if let Some(depth) = self.returns.iter().rposition(|ret| *ret == pc) {
+                        self.returns.truncate(depth);
+                        self.stack.truncate(depth + 1);
+                    } else if self.written_in_block.contains(&(next as i128)) {
+                        if self.stack.len() < MAX_STACK_DEPTH {
+                            self.returns.push(next);
+                            self.stack.push(pc);
+                        }
+                    } else if pc <= previous_pc {
+                        *self.back_edges.entry((previous_pc, pc)).or_insert(0) += 1;
+                    }
+                }
+                if jumped || matches!(previous, JumpIfTrue5(..) | JumpIfFalse6(..)) {
+                    self.enter_block(pc);
+                }
+            }
+        }
+
+        self.total += 1;
+        self.addresses.entry(pc).or_insert((instruction, 0)).1 += 1;
+        *self.instructions.entry(instruction).or_insert(0) += 1;
+        self.blocks.get_mut(&self.block).unwrap().1 += 1;
+        match self.stacks.get_mut(&self.stack[..]) {
+            Some(count) => *count += 1,
+            None => {
+                self.stacks.insert(self.stack.clone(), 1);
+            }
+        }
+        self.previous = Some((pc, instruction));
+    }
+
+    fn write(&mut self, _addr: usize, value: i128) {
+        self.written_in_block.push(value);
+    }
+
+    fn waiting_for_input(&mut self) {
+        self.waiting = true;
+    }
+}
+
+impl IntCodeComputer {
+    // turns on profiling, the profiler keeps counting for as long as the machine runs. this
+    // takes the place of any other tracer
+    pub fn start_profiling(&mut self) -> Arc<Mutex<Profiler>> {
+        let profiler = Arc::new(Mutex::new(Profiler::new()));
+        self.trace_to(profiler.clone());
+        profiler
+    }
+}
+
+#[test]
+fn counts_loops_and_calls() {
+    use crate::intcode::assemble::assemble;
+
+    // counts down from 3, calling a function to do the decrementing
+    let proggy = assemble(
+        "
+        arb 100
+        add 3, 0, [rb+1]
+        loop: call decrement
+        jnz [rb+1], loop
+        halt
+        decrement: add [rb+0], -1, [rb+0]
+        ret
+        ",
+    )
+    .unwrap();
+    let mut icc = IntCodeComputer::from_ints(proggy);
+    let profiler = icc.start_profiling();
+    icc.run_until_halt();
+    let profiler = profiler.lock().unwrap();
+
+    // two to set up, then three times round a 3 instruction call, the decrement, a 2
+    // instruction return and the jnz, then the halt
+    assert_eq!(2 + 3 * (3 + 1 + 2 + 1) + 1, profiler.total());
+    assert_eq!(3, profiler.address_count(19));
+    // returning isn't a loop
+    assert_eq!(
+        vec![(&(15, 6), &2)],
+        profiler.back_edges.iter().collect_vec()
+    );
+    assert_eq!(
+        vec!["L0 15", "L0;L19 9"],
+        profiler.collapsed_stacks().lines().collect_vec()
+    );
+    assert!(profiler.report(3).contains("L19: entered 3 times"));
+}
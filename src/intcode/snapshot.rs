@@ -0,0 +1,357 @@
+use crate::intcode::arithmetic::Arithmetic;
+use crate::intcode::decode::DecodeCache;
+use crate::intcode::limits::Limits;
+use crate::intcode::{IntCodeComputer, Memory};
+use itertools::Itertools;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::path::Path;
+
+// a saved machine is a small text file, one field per line:
+//
+//     intcode-snapshot 2
+//     current_pos 1234
+//     relative_base 2000
+//     num_instructions_processed 56789
+//     input 78,101,115
+//     memory 109,4787,...
+//     sparse 1000000000=5
+//     arithmetic checked
+//     limits 100000,none,none
+//     detect_loops yes
+//
+// input is in the order the machine will read it. memory is the contiguous part of memory and
+// sparse holds the far away cells, as address=value pairs. limits are steps, stored cells and
+// address, in that order. tracing and whether the decode cache was on aren't saved, a loaded
+// machine has a fresh cache and no tracer. neither is what the loop detector has seen so far, it
+// starts looking again from where the machine was loaded.
+//
+// the version goes up whenever the format changes, and older versions get read for as long as
+// we can still make sense of them. version 1 stopped at sparse, machines loaded from it get the
+// default arithmetic, no limits and no loop detection
+const HEADER: &str = "intcode-snapshot";
+const VERSION: u32 = 2;
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(std::io::Error),
+    UnsupportedVersion(String),
+    // line numbers start at 1
+    Malformed { line: usize, message: String },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SnapshotError::Io(e) => write!(f, "{}", e),
+            SnapshotError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported snapshot version {}, this build reads version {}",
+                version, VERSION
+            ),
+            SnapshotError::Malformed { line, message } => {
+                write!(f, "bad snapshot, line {}: {}", line, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<std::io::Error> for SnapshotError {
+    fn from(e: std::io::Error) -> Self {
+        SnapshotError::Io(e)
+    }
+}
+
+fn malformed(line: usize, message: impl Into<String>) -> SnapshotError {
+    SnapshotError::Malformed {
+        line,
+        message: message.into(),
+    }
+}
+
+fn number<T: std::str::FromStr>((line, s): (usize, &str)) -> Result<T, SnapshotError> {
+    s.parse()
+        .map_err(|_| malformed(line, format!("expected a number, got {:?}", s)))
+}
+
+fn limit_to_string(limit: usize) -> String {
+    if limit == usize::MAX {
+        "none".to_owned()
+    } else {
+        limit.to_string()
+    }
+}
+
+fn parse_limit((line, s): (usize, &str)) -> Result<usize, SnapshotError> {
+    if s == "none" {
+        Ok(usize::MAX)
+    } else {
+        number((line, s))
+    }
+}
+
+fn parse_list(line: usize, s: &str) -> Result<Vec<i128>, SnapshotError> {
+    if s.is_empty() {
+        return Ok(vec![]);
+    }
+    s.split(',').map(|n| number((line, n))).collect()
+}
+
+impl IntCodeComputer {
+    pub fn to_snapshot(&self) -> String {
+        let sparse = self
+            .memory
+            .sparse()
+            .iter()
+            .sorted()
+            .map(|(addr, value)| format!("{}={}", addr, value))
+            .join(",");
+        let lines = [
+            format!("{} {}", HEADER, VERSION),
+            format!("current_pos {}", self.current_pos),
+            format!("relative_base {}", self.relative_base),
+            format!(
+                "num_instructions_processed {}",
+                self.num_instructions_processed
+            ),
+            format!("input {}", self.input.iter().rev().join(",")),
+            format!("memory {}", self.memory.dense().join(",")),
+            format!("sparse {}", sparse),
+            format!(
+                "arithmetic {}",
+                match self.arithmetic {
+                    Arithmetic::Checked => "checked",
+                    Arithmetic::Wrapping => "wrapping",
+                }
+            ),
+            format!(
+                "limits {},{},{}",
+                limit_to_string(self.limits.steps),
+                limit_to_string(self.limits.stored_cells),
+                limit_to_string(self.limits.address)
+            ),
+            format!(
+                "detect_loops {}",
+                if self.loop_detector.is_some() {
+                    "yes"
+                } else {
+                    "no"
+                }
+            ),
+        ];
+        lines.join("\n") + "\n"
+    }
+
+    pub fn from_snapshot(snapshot: &str) -> Result<Self, SnapshotError> {
+        let num_lines = snapshot.lines().count();
+        let mut lines = snapshot.lines().enumerate().map(|(i, line)| (i + 1, line));
+        let version = match lines.next().map(|(_, line)| line.split(' ').collect_vec()) {
+            Some(ref header) if header.len() == 2 && header[0] == HEADER => {
+                match header[1].parse() {
+                    Ok(version) if (1..=VERSION).contains(&version) => version,
+                    _ => return Err(SnapshotError::UnsupportedVersion(header[1].to_owned())),
+                }
+            }
+            _ => return Err(malformed(1, "not an intcode snapshot")),
+        };
+
+        let mut field = |name: &str| {
+            let (n, line) = lines
+                .next()
+                .ok_or_else(|| malformed(num_lines + 1, format!("missing {}", name)))?;
+            match line.split_once(' ') {
+                Some((key, value)) if key == name => Ok((n, value.trim())),
+                None if line == name => Ok((n, "")),
+                _ => Err(malformed(n, format!("expected {}", name))),
+            }
+        };
+        let current_pos = number(field("current_pos")?)?;
+        let relative_base = number(field("relative_base")?)?;
+        let num_instructions_processed = number(field("num_instructions_processed")?)?;
+        let (n, input) = field("input")?;
+        let input = parse_list(n, input)?;
+        let (n, dense) = field("memory")?;
+        let dense = parse_list(n, dense)?;
+        let (n, sparse) = field("sparse")?;
+        let mut sparse_cells = HashMap::new();
+        for cell in sparse.split(',').filter(|cell| !cell.is_empty()) {
+            let (addr, value) = cell
+                .split_once('=')
+                .ok_or_else(|| malformed(n, format!("expected address=value, got {:?}", cell)))?;
+            let addr = number((n, addr))?;
+            if addr < dense.len() {
+                return Err(malformed(
+                    n,
+                    format!("sparse cell {} is in dense memory", addr),
+                ));
+            }
+            sparse_cells.insert(addr, number((n, value))?);
+        }
+        let memory = Memory::from_parts(dense, sparse_cells);
+
+        let mut arithmetic = Arithmetic::default();
+        let mut limits = Limits::none();
+        let mut detect_loops = false;
+        if version >= 2 {
+            let (n, name) = field("arithmetic")?;
+            arithmetic = match name {
+                "checked" => Arithmetic::Checked,
+                "wrapping" => Arithmetic::Wrapping,
+                _ => return Err(malformed(n, format!("unknown arithmetic {:?}", name))),
+            };
+            let (n, list) = field("limits")?;
+            let list = list.split(',').map(|limit| parse_limit((n, limit)));
+            match list.collect::<Result<Vec<_>, _>>()?[..] {
+                [steps, stored_cells, address] => {
+                    limits = Limits::none()
+                        .steps(steps)
+                        .stored_cells(stored_cells)
+                        .address(address)
+                }
+                _ => return Err(malformed(n, "expected steps,stored_cells,address")),
+            }
+            let (n, yes_or_no) = field("detect_loops")?;
+            detect_loops = match yes_or_no {
+                "yes" => true,
+                "no" => false,
+                _ => {
+                    return Err(malformed(
+                        n,
+                        format!("expected yes or no, got {:?}", yes_or_no),
+                    ))
+                }
+            };
+        }
+
+        let mut icc = Self {
+            num_instructions_processed,
+            decode_cache: DecodeCache::new(&memory),
+            memory,
+            input: input.into_iter().rev().collect::<VecDeque<_>>(),
+            current_pos,
+            relative_base,
+            tracer: None,
+            limits,
+            loop_detector: None,
+            arithmetic,
+        };
+        if detect_loops {
+            icc.detect_loops();
+        }
+        Ok(icc)
+    }
+
+    pub fn save_snapshot(&self, path: impl AsRef<Path>) -> Result<(), SnapshotError> {
+        Ok(std::fs::write(path, self.to_snapshot())?)
+    }
+
+    pub fn load_snapshot(path: impl AsRef<Path>) -> Result<Self, SnapshotError> {
+        Self::from_snapshot(&std::fs::read_to_string(path)?)
+    }
+}
+
+#[test]
+fn restored_machines_pick_up_where_they_left_off() {
+    // adds up inputs until it reads a 0, keeping the total way out at [1000000] so it ends up
+    // in sparse memory:
+    //
+    //      0: in   [100]
+    //      2: jz   [100], 12
+    //      5: add  [100], [1000000], [1000000]
+    //      9: jnz  1, 0
+    //     12: out  [1000000]
+    //     14: halt
+    let proggy = vec![
+        3, 100, 1006, 100, 12, 1, 100, 1000000, 1000000, 1105, 1, 0, 4, 1000000, 99,
+    ];
+    let mut icc = IntCodeComputer::from_ints(proggy);
+    icc.queue_input(5);
+    icc.queue_input(6);
+    icc.queue_input(7);
+    // reads 5 and 6, then stops right before the third input
+    for _ in 0..8 {
+        icc.run_one_traced();
+    }
+    let snapshot = icc.to_snapshot();
+    assert!(snapshot.contains("\ninput 7\n"));
+    assert!(snapshot.contains("\nsparse 1000000=11\n"));
+
+    let mut restored = IntCodeComputer::from_snapshot(&snapshot).unwrap();
+    assert_eq!(snapshot, restored.to_snapshot());
+    restored.queue_input(0);
+    assert_eq!(vec![18], restored.run_until_halt());
+
+    icc.queue_input(0);
+    assert_eq!(vec![18], icc.run_until_halt());
+    assert_eq!(
+        icc.num_instructions_processed,
+        restored.num_instructions_processed
+    );
+}
+
+#[test]
+fn rejects_other_versions_and_garbage() {
+    let snapshot = IntCodeComputer::from_ints(vec![99]).to_snapshot();
+    assert!(IntCodeComputer::from_snapshot(&snapshot).is_ok());
+    assert!(matches!(
+        IntCodeComputer::from_snapshot(&snapshot.replace("snapshot 2", "snapshot 3")),
+        Err(SnapshotError::UnsupportedVersion(_))
+    ));
+    assert!(matches!(
+        IntCodeComputer::from_snapshot(&snapshot.replace("memory 99", "memory 9x")),
+        Err(SnapshotError::Malformed { line: 6, .. })
+    ));
+    assert!(IntCodeComputer::from_snapshot("1,2,3").is_err());
+}
+
+#[test]
+fn settings_come_back_too() {
+    use crate::intcode::IntcodeErrorKind;
+
+    // doubles i128::MAX, which wraps around to -2, and then jumps to itself forever
+    let mut icc = IntCodeComputer::from_ints(vec![1102, i128::MAX, 2, 9, 1105, 1, 4, 99, 0, 0]);
+    icc.set_arithmetic(Arithmetic::Wrapping);
+    icc.set_limits(Limits::none().steps(100));
+    icc.detect_loops();
+    let snapshot = icc.to_snapshot();
+    assert!(snapshot.ends_with("arithmetic wrapping\nlimits 100,none,none\ndetect_loops yes\n"));
+    let mut restored = IntCodeComputer::from_snapshot(&snapshot).unwrap();
+    assert_eq!(snapshot, restored.to_snapshot());
+    assert!(matches!(
+        restored.try_run_until_halt().unwrap_err().kind,
+        IntcodeErrorKind::InfiniteLoop { entry: 4, .. }
+    ));
+    assert_eq!(-2, restored.read_memory(9));
+
+    // the last version didn't have them at all
+    let old = snapshot.replace("snapshot 2", "snapshot 1").replace(
+        "arithmetic wrapping\nlimits 100,none,none\ndetect_loops yes\n",
+        "",
+    );
+    let old = IntCodeComputer::from_snapshot(&old).unwrap();
+    assert_eq!(Arithmetic::Checked, old.arithmetic);
+    assert_eq!(Limits::none(), old.limits());
+    assert!(old.loop_detector.is_none());
+}
+
+#[test]
+fn sparse_cells_stay_sparse() {
+    // 5000 is too far past the end to grow into, then the write to 1000 brings the end close
+    // enough that writing 5000 now would have. it's still sparse, and stays that way on reload
+    let mut icc = IntCodeComputer::from_ints(vec![1101, 7, 0, 5000, 1101, 8, 0, 1000, 99]);
+    icc.run_until_halt();
+    let snapshot = icc.to_snapshot();
+    assert!(snapshot.contains("\nsparse 5000=7\n"));
+    let restored = IntCodeComputer::from_snapshot(&snapshot).unwrap();
+    assert_eq!(icc.memory.cells(), restored.memory.cells());
+    assert_eq!(snapshot, restored.to_snapshot());
+
+    // a sparse cell the dense part already covers can't have come from a real machine
+    let overlapping = snapshot.replace("sparse 5000=7", "sparse 5000=7,3=1");
+    assert!(matches!(
+        IntCodeComputer::from_snapshot(&overlapping),
+        Err(SnapshotError::Malformed { line: 7, .. })
+    ));
+}
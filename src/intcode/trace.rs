@@ -0,0 +1,181 @@
+use crate::intcode::Instruction::{
+    Add1, Equals8, Halt99, Input3, JumpIfFalse6, JumpIfTrue5, LessThan7, Multiply2, Output4,
+    RelativeBaseOffset9,
+};
+use crate::intcode::ParameterMode::{ImmediateMode1, PositionMode0, RelativeMode2};
+use crate::intcode::{Instruction, IntcodeError};
+use itertools::Itertools;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+// gets told about everything the machine does, one instruction at a time. every step starts with
+// begin and finishes with end, the rest get called in between as they happen. all of these do
+// nothing by default.
+//
+// machines without a tracer run their loop with NoTrace, where all the calls inline away, so
+// tracing costs nothing unless it's turned on
+pub trait Tracer {
+    // instruction is None when the cell at pc isn't a valid instruction, end gets the error next
+    fn begin(&mut self, _step: usize, _pc: usize, _instruction: Option<Instruction>) {}
+    // the resolved value of a param that's read, in the order they're read
+    fn read(&mut self, _value: i128) {}
+    fn write(&mut self, _addr: usize, _value: i128) {}
+    fn relative_base(&mut self, _from: i128, _to: i128) {}
+    fn input(&mut self, _value: i128) {}
+    // an input instruction found the input queue empty, so it'll run again once there's input
+    fn waiting_for_input(&mut self) {}
+    fn output(&mut self, _value: i128) {}
+    fn end(&mut self, _error: Option<&IntcodeError>) {}
+}
+
+// what a machine holds on to while it's being traced. it's shared so whoever turned tracing on
+// can still get at the tracer, and clones of a machine keep tracing into the same one
+pub type SharedTracer = Arc<Mutex<dyn Tracer + Send>>;
+
+pub struct NoTrace;
+
+impl Tracer for NoTrace {}
+
+fn opcode(instruction: &Instruction) -> u32 {
+    match instruction {
+        Add1(..) => 1,
+        Multiply2(..) => 2,
+        Input3(_) => 3,
+        Output4(_) => 4,
+        JumpIfTrue5(..) => 5,
+        JumpIfFalse6(..) => 6,
+        LessThan7(..) => 7,
+        Equals8(..) => 8,
+        RelativeBaseOffset9(_) => 9,
+        Halt99 => 99,
+    }
+}
+
+// writes one json object per executed instruction, e.g.
+//
+//     {"step":3,"pc":4,"opcode":1,"mnemonic":"add","modes":[0,1,2],"reads":[7,3],
+//      "writes":[[1002,10]]}
+//
+// writes are [address, value] pairs. "rb" ([from, to]), "input", "output", "waiting_for_input"
+// and "error" are only there for the steps they happened on. invalid instructions have no
+// opcode, mnemonic or modes, just the error
+pub struct JsonLinesTracer<W: Write> {
+    out: W,
+    instruction: Vec<String>,
+    reads: Vec<i128>,
+    writes: Vec<(usize, i128)>,
+    events: Vec<String>,
+}
+
+impl JsonLinesTracer<BufWriter<File>> {
+    pub fn create(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> JsonLinesTracer<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            instruction: vec![],
+            reads: vec![],
+            writes: vec![],
+            events: vec![],
+        }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.out
+    }
+}
+
+impl<W: Write> Tracer for JsonLinesTracer<W> {
+    fn begin(&mut self, step: usize, pc: usize, instruction: Option<Instruction>) {
+        self.instruction.clear();
+        self.reads.clear();
+        self.writes.clear();
+        self.events.clear();
+        self.instruction.push(format!("\"step\":{}", step));
+        self.instruction.push(format!("\"pc\":{}", pc));
+        if let Some(instruction) = instruction {
+            let mut modes = instruction.modes().into_iter().map(|mode| match mode {
+                PositionMode0 => 0,
+                ImmediateMode1 => 1,
+                RelativeMode2 => 2,
+            });
+            self.instruction
+                .push(format!("\"opcode\":{}", opcode(&instruction)));
+            self.instruction
+                .push(format!("\"mnemonic\":\"{}\"", instruction.mnemonic()));
+            self.instruction
+                .push(format!("\"modes\":[{}]", modes.join(",")));
+        }
+    }
+
+    fn read(&mut self, value: i128) {
+        self.reads.push(value);
+    }
+
+    fn write(&mut self, addr: usize, value: i128) {
+        self.writes.push((addr, value));
+    }
+
+    fn relative_base(&mut self, from: i128, to: i128) {
+        self.events.push(format!("\"rb\":[{},{}]", from, to));
+    }
+
+    fn input(&mut self, value: i128) {
+        self.events.push(format!("\"input\":{}", value));
+    }
+
+    fn waiting_for_input(&mut self) {
+        self.events.push("\"waiting_for_input\":true".to_owned());
+    }
+
+    fn output(&mut self, value: i128) {
+        self.events.push(format!("\"output\":{}", value));
+    }
+
+    fn end(&mut self, error: Option<&IntcodeError>) {
+        let writes = self
+            .writes
+            .iter()
+            .map(|(addr, value)| format!("[{},{}]", addr, value))
+            .join(",");
+        let mut fields = self.instruction.clone();
+        fields.push(format!("\"reads\":[{}]", self.reads.iter().join(",")));
+        fields.push(format!("\"writes\":[{}]", writes));
+        fields.append(&mut self.events);
+        if let Some(error) = error {
+            // the error messages don't have anything in them that needs escaping
+            fields.push(format!("\"error\":\"{}\"", error));
+        }
+        // a trace that can't be written isn't worth stopping the machine over
+        let _ = writeln!(self.out, "{{{}}}", fields.join(","));
+    }
+}
+
+#[test]
+fn traces_one_json_object_per_instruction() {
+    use crate::intcode::IntCodeComputer;
+
+    // arb 20, in [rb+0], mul [rb+0], 2, [21], out [21], halt
+    let mut icc = IntCodeComputer::from_ints(vec![109, 20, 203, 0, 1202, 0, 2, 21, 4, 21, 99]);
+    let tracer = Arc::new(Mutex::new(JsonLinesTracer::new(vec![])));
+    icc.trace_to(tracer.clone());
+    icc.queue_input(21);
+    assert_eq!(vec![42], icc.run_until_halt());
+    let trace = String::from_utf8(tracer.lock().unwrap().get_ref().clone()).unwrap();
+    assert_eq!(
+        vec![
+            r#"{"step":0,"pc":0,"opcode":9,"mnemonic":"arb","modes":[1],"reads":[20],"writes":[],"rb":[0,20]}"#,
+            r#"{"step":1,"pc":2,"opcode":3,"mnemonic":"in","modes":[2],"reads":[],"writes":[[20,21]],"input":21}"#,
+            r#"{"step":2,"pc":4,"opcode":2,"mnemonic":"mul","modes":[2,1,0],"reads":[21,2],"writes":[[21,42]]}"#,
+            r#"{"step":3,"pc":8,"opcode":4,"mnemonic":"out","modes":[0],"reads":[42],"writes":[],"output":42}"#,
+            r#"{"step":4,"pc":10,"opcode":99,"mnemonic":"halt","modes":[],"reads":[],"writes":[]}"#,
+        ],
+        trace.lines().collect_vec()
+    );
+}
@@ -0,0 +1,575 @@
+use crate::intcode::arithmetic::Arithmetic;
+use crate::intcode::cfg::control_flow_graph;
+use crate::intcode::decode::DecodeCache;
+use crate::intcode::disassemble::{analyze, decode_at, format_instruction};
+use crate::intcode::limits::Limits;
+use crate::intcode::Instruction::{
+    Add1, Equals8, Halt99, Input3, JumpIfFalse6, JumpIfTrue5, LessThan7, Multiply2, Output4,
+    RelativeBaseOffset9,
+};
+use crate::intcode::ParameterMode::{ImmediateMode1, PositionMode0, RelativeMode2};
+use crate::intcode::{Instruction, IntCodeComputer, Memory, ParameterMode, RunResult};
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::convert::TryFrom;
+
+// programs that were transpiled ahead of time, made with `transpile <program>`. they're only
+// built for the tests below, which check them against their programs, so regenerate them when the
+// transpiler changes
+#[cfg(test)]
+#[rustfmt::skip]
+#[allow(clippy::all)]
+pub mod day19_beam;
+#[cfg(test)]
+#[rustfmt::skip]
+#[allow(clippy::all)]
+pub mod day9_boost;
+#[cfg(test)]
+#[rustfmt::skip]
+#[allow(clippy::all)]
+pub mod overflows;
+#[cfg(test)]
+#[rustfmt::skip]
+#[allow(clippy::all)]
+pub mod patches_itself;
+
+// a program turned into rust by transpile: its image, which of its cells are code (one bit per
+// cell) and the compiled code, which runs the machine until it has something to return
+pub struct Compiled {
+    pub proggy: &'static [i128],
+    pub code: &'static [u64],
+    pub run: fn(&mut Machine) -> RunResult,
+}
+
+// runs a compiled program with the same protocol as IntCodeComputer. the compiled code keeps
+// going for as long as the program does what it did when it was transpiled, and the first time
+// it does something else (writes over its own code, jumps somewhere that wasn't code, uses a bad
+// address) the machine gets handed over to the interpreter, right before the instruction that did
+// it, and the interpreter runs it from then on. the parts of it only compiled code uses go unused
+// outside of the tests, since that's the only place the generated programs get built
+#[derive(Clone)]
+#[cfg_attr(not(test), allow(dead_code))]
+pub struct Machine {
+    program: &'static Compiled,
+    memory: Memory,
+    input: VecDeque<i128>,
+    pc: usize,
+    rb: i128,
+    num_instructions_processed: usize,
+    interpreter: Option<IntCodeComputer>,
+}
+
+// same as the interpreter, negative addresses and ones too big for a usize are errors
+#[cfg_attr(not(test), allow(dead_code))]
+fn address(raw: i128) -> Option<usize> {
+    usize::try_from(raw).ok()
+}
+
+impl Machine {
+    pub fn new(program: &'static Compiled) -> Self {
+        Self {
+            program,
+            memory: Memory::new(program.proggy.to_vec()),
+            input: VecDeque::new(),
+            pc: 0,
+            rb: 0,
+            num_instructions_processed: 0,
+            interpreter: None,
+        }
+    }
+
+    pub fn queue_input(&mut self, input: i128) {
+        match &mut self.interpreter {
+            Some(icc) => icc.queue_input(input),
+            None => self.input.push_front(input),
+        }
+    }
+
+    pub fn run_and_get_next(&mut self) -> RunResult {
+        match &mut self.interpreter {
+            Some(icc) => icc.run_and_get_next(),
+            None => (self.program.run)(self),
+        }
+    }
+
+    pub fn run_until_halt(&mut self) -> Vec<i128> {
+        let mut all_output = vec![];
+        loop {
+            match self.run_and_get_next() {
+                RunResult::Output(output) => all_output.push(output),
+                RunResult::Halt => return all_output,
+                RunResult::NeedMoreInput => panic!("ran out of input"),
+                RunResult::Error(e) => panic!("{}", e),
+            }
+        }
+    }
+
+    pub fn num_instructions_processed(&self) -> usize {
+        match &self.interpreter {
+            Some(icc) => icc.num_instructions_processed,
+            None => self.num_instructions_processed,
+        }
+    }
+
+    pub fn is_interpreting(&self) -> bool {
+        self.interpreter.is_some()
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    fn is_code(&self, addr: usize) -> bool {
+        matches!(self.program.code.get(addr / 64), Some(bits) if bits >> (addr % 64) & 1 == 1)
+    }
+
+    // the compiled code counts a run of instructions before it starts on them, not_run is how
+    // many of those it didn't get to
+    #[cfg_attr(not(test), allow(dead_code))]
+    fn fall_back(&mut self, pc: usize, not_run: usize) -> RunResult {
+        let memory = std::mem::take(&mut self.memory);
+        self.interpreter = Some(IntCodeComputer {
+            num_instructions_processed: self.num_instructions_processed - not_run,
+            decode_cache: DecodeCache::new(&memory),
+            memory,
+            input: std::mem::take(&mut self.input),
+            current_pos: pc,
+            relative_base: self.rb,
+            tracer: None,
+            limits: Limits::none(),
+            loop_detector: None,
+            arithmetic: Arithmetic::default(),
+        });
+        self.interpreter.as_mut().unwrap().run_and_get_next()
+    }
+}
+
+// the code for one instruction of a unit. units are what the compiled code dispatches on: runs of
+// instructions that start at a jump target, an input (which runs again once there's input) or
+// right after an output, and end at a jump, a halt or an output
+struct Emitter<'a> {
+    lines: &'a mut Vec<String>,
+    proggy: &'a [i128],
+    // cells that hold compiled code, writing to one of these falls back
+    code: &'a BTreeSet<usize>,
+    // operands the program writes over at addresses we can tell statically, these get read from
+    // memory instead of being compiled in
+    patched: &'a HashSet<usize>,
+    pc: usize,
+    // how many instructions of the unit are left, counting this one
+    not_run: usize,
+}
+
+impl<'a> Emitter<'a> {
+    fn line(&mut self, line: impl Into<String>) {
+        self.lines.push(format!("                {}", line.into()));
+    }
+
+    fn fall_back(&self) -> String {
+        format!("return m.fall_back({}, {})", self.pc, self.not_run)
+    }
+
+    // the operand as it is in the program, if it can't change
+    fn constant(&self, i: usize) -> Option<i128> {
+        let cell = self.pc + 1 + i;
+        match self.patched.contains(&cell) {
+            true => None,
+            false => Some(self.proggy[cell]),
+        }
+    }
+
+    fn operand(&self, i: usize) -> String {
+        match self.constant(i) {
+            Some(operand) => operand.to_string(),
+            None => format!("m.memory[{}]", self.pc + 1 + i),
+        }
+    }
+
+    // an address only known at run time goes into a variable, and falls back if it's bad
+    fn address(&mut self, raw: String, name: &str) -> String {
+        let fall_back = self.fall_back();
+        self.line(format!(
+            "let {} = match address({}) {{ Some(addr) => addr, None => {} }};",
+            name, raw, fall_back
+        ));
+        name.to_owned()
+    }
+
+    // an address too far out to add to the relative base saturates, which is a bad address too
+    fn relative(&mut self, i: usize, name: &str) -> String {
+        let raw = match self.constant(i) {
+            Some(0) => "m.rb".to_owned(),
+            _ => format!("m.rb.saturating_add({})", self.operand(i)),
+        };
+        self.address(raw, name)
+    }
+
+    // a sum or product only known at run time, which falls back if it overflows so the
+    // interpreter can report it
+    fn checked(&self, op: &str, a: &str, b: &str) -> String {
+        format!(
+            "match i128::checked_{}({}, {}) {{ Some(value) => value, None => {} }}",
+            op,
+            a,
+            b,
+            self.fall_back()
+        )
+    }
+
+    fn mode(&self, i: usize) -> ParameterMode {
+        decode_at(self.proggy, self.pc).unwrap().0.modes()[i]
+    }
+
+    // None if the instruction can only ever fall back
+    fn read(&mut self, i: usize, name: &str) -> Option<String> {
+        let addr = match (self.mode(i), self.constant(i)) {
+            (ImmediateMode1, _) => return Some(self.operand(i)),
+            (PositionMode0, Some(operand)) => address(operand)?.to_string(),
+            (PositionMode0, None) => self.address(self.operand(i), name),
+            (RelativeMode2, _) => self.relative(i, name),
+        };
+        Some(format!("m.memory[{}]", addr))
+    }
+
+    // where a result goes. a write over code falls back, whether we can tell now or only once
+    // it's running
+    fn write_address(&mut self, i: usize) -> Option<String> {
+        let addr = match (self.mode(i), self.constant(i)) {
+            (ImmediateMode1, _) => return None,
+            (PositionMode0, Some(operand)) => {
+                return address(operand)
+                    .filter(|addr| !self.code.contains(addr))
+                    .map(|addr| addr.to_string())
+            }
+            (PositionMode0, None) => self.address(self.operand(i), "dst"),
+            (RelativeMode2, _) => self.relative(i, "dst"),
+        };
+        let fall_back = self.fall_back();
+        self.line(format!("if m.is_code(dst) {{ {} }}", fall_back));
+        Some(addr)
+    }
+
+    // emits the instruction, false if control doesn't carry on to the next one in the unit
+    fn instruction(&mut self) -> bool {
+        let (instruction, operands) = decode_at(self.proggy, self.pc).unwrap();
+        self.line(format!(
+            "// {}: {}",
+            self.pc,
+            format_instruction(&instruction, operands, &BTreeSet::new())
+        ));
+        let start = self.lines.len();
+        match self.try_instruction(instruction) {
+            Some(carries_on) => carries_on,
+            None => {
+                self.lines.truncate(start);
+                let fall_back = self.fall_back();
+                self.line(format!("{};", fall_back));
+                false
+            }
+        }
+    }
+
+    fn try_instruction(&mut self, instruction: Instruction) -> Option<bool> {
+        let next = self.pc + instruction.size();
+        match instruction {
+            Add1(..) | Multiply2(..) | LessThan7(..) | Equals8(..) => {
+                let a = self.read(0, "a")?;
+                let b = self.read(1, "b")?;
+                let dst = self.write_address(2)?;
+                let constants = match (self.mode(0), self.mode(1)) {
+                    (ImmediateMode1, ImmediateMode1) => (self.constant(0), self.constant(1)),
+                    (ImmediateMode1, _) => (self.constant(0), None),
+                    (_, ImmediateMode1) => (None, self.constant(1)),
+                    _ => (None, None),
+                };
+                // constants that overflow make the whole instruction fall back, and the
+                // interpreter stops on it with an Overflow error
+                let value = match (instruction, constants) {
+                    (Add1(..), (Some(x), Some(y))) => x.checked_add(y)?.to_string(),
+                    (Add1(..), (Some(0), _)) => b,
+                    (Add1(..), (_, Some(0))) => a,
+                    (Add1(..), _) => self.checked("add", &a, &b),
+                    (Multiply2(..), (Some(x), Some(y))) => x.checked_mul(y)?.to_string(),
+                    (Multiply2(..), (Some(0), _)) | (Multiply2(..), (_, Some(0))) => "0".to_owned(),
+                    (Multiply2(..), (Some(1), _)) => b,
+                    (Multiply2(..), (_, Some(1))) => a,
+                    (Multiply2(..), _) => self.checked("mul", &a, &b),
+                    (LessThan7(..), (Some(x), Some(y))) => ((x < y) as i128).to_string(),
+                    (LessThan7(..), _) if a == b => "0".to_owned(),
+                    (LessThan7(..), _) => format!("({} < {}) as i128", a, b),
+                    (_, (Some(x), Some(y))) => ((x == y) as i128).to_string(),
+                    _ if a == b => "1".to_owned(),
+                    _ => format!("({} == {}) as i128", a, b),
+                };
+                self.line(format!("let value = {};", value));
+                self.line(format!("m.memory[{}] = value;", dst));
+            }
+            Input3(_) => {
+                let dst = self.write_address(0)?;
+                // waiting for input doesn't count as running the input instruction, same as in
+                // the interpreter
+                self.line("let input = match m.input.pop_back() {");
+                self.line("    Some(input) => input,");
+                self.line(format!(
+                    "    None => {{ m.num_instructions_processed -= {}; return RunResult::NeedMoreInput; }}",
+                    self.not_run
+                ));
+                self.line("};");
+                self.line(format!("m.memory[{}] = input;", dst));
+            }
+            Output4(_) => {
+                let value = self.read(0, "a")?;
+                self.line(format!("let value = {};", value));
+                self.line(format!("m.pc = {};", next));
+                self.line("return RunResult::Output(value);");
+                return Some(false);
+            }
+            RelativeBaseOffset9(_) => {
+                let by = self.read(0, "a")?;
+                let rb = self.checked("add", "m.rb", &by);
+                self.line(format!("m.rb = {};", rb));
+            }
+            JumpIfTrue5(..) | JumpIfFalse6(..) => {
+                let cond = self.read(0, "a")?;
+                let target = self.read(1, "b")?;
+                let constant = |i| match self.mode(i) {
+                    ImmediateMode1 => self.constant(i),
+                    _ => None,
+                };
+                let jump = match constant(1) {
+                    Some(target) => match address(target) {
+                        Some(target) => format!("m.pc = {};", target),
+                        None => format!("{};", self.fall_back()),
+                    },
+                    None => format!(
+                        "m.pc = match address({}) {{ Some(target) => target, None => {} }};",
+                        target,
+                        self.fall_back()
+                    ),
+                };
+                let jumps_if_nonzero = matches!(instruction, JumpIfTrue5(..));
+                match constant(0) {
+                    Some(cond) if (cond != 0) == jumps_if_nonzero => self.line(jump),
+                    Some(_) => self.line(format!("m.pc = {};", next)),
+                    None => {
+                        let op = if jumps_if_nonzero { "!=" } else { "==" };
+                        self.line(format!("if {} {} 0 {{", cond, op));
+                        self.line(format!("    {}", jump));
+                        self.line("} else {");
+                        self.line(format!("    m.pc = {};", next));
+                        self.line("}");
+                    }
+                }
+                return Some(false);
+            }
+            Halt99 => {
+                self.line(format!("m.pc = {};", self.pc));
+                self.line("return RunResult::Halt;");
+                return Some(false);
+            }
+        }
+        Some(true)
+    }
+}
+
+fn wrapped(values: impl Iterator<Item = String>) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = String::new();
+    for value in values {
+        if !line.is_empty() && line.len() + value.len() + 2 > 88 {
+            lines.push(format!("        {}", line.trim_end()));
+            line.clear();
+        }
+        line += &value;
+        line += ", ";
+    }
+    if !line.is_empty() {
+        lines.push(format!("        {}", line.trim_end()));
+    }
+    lines
+}
+
+// rust source for a module that runs proggy, to be declared next to the ones above and run with
+// Machine::new(&that_module::PROGRAM). everything the static analysis finds is compiled, one
+// match arm per unit, and the interpreter takes over wherever it missed something
+pub fn transpile(proggy: &[i128]) -> String {
+    let analysis = analyze(proggy);
+    let patched = analysis
+        .instructions
+        .iter()
+        .flat_map(|(addr, instruction)| addr + 1..addr + instruction.size())
+        .filter(|cell| analysis.written.contains(cell))
+        .collect::<HashSet<_>>();
+    let code = analysis
+        .instructions
+        .iter()
+        .flat_map(|(addr, instruction)| *addr..addr + instruction.size())
+        .filter(|cell| !patched.contains(cell))
+        .collect::<BTreeSet<_>>();
+    let mut units = vec![];
+    for block in control_flow_graph(proggy).blocks.values() {
+        let mut unit = vec![];
+        for &addr in &block.instructions {
+            let instruction = analysis.instructions[&addr];
+            if matches!(instruction, Input3(_)) && !unit.is_empty() {
+                units.push(std::mem::take(&mut unit));
+            }
+            unit.push(addr);
+            if matches!(instruction, Output4(_)) {
+                units.push(std::mem::take(&mut unit));
+            }
+        }
+        if !unit.is_empty() {
+            units.push(unit);
+        }
+    }
+
+    let mut arms = vec![];
+    for unit in units {
+        arms.push(format!("            {} => {{", unit[0]));
+        arms.push(format!(
+            "                m.num_instructions_processed += {};",
+            unit.len()
+        ));
+        for (i, addr) in unit.iter().enumerate() {
+            let mut emitter = Emitter {
+                lines: &mut arms,
+                proggy,
+                code: &code,
+                patched: &patched,
+                pc: *addr,
+                not_run: unit.len() - i,
+            };
+            if !emitter.instruction() {
+                break;
+            }
+            if i == unit.len() - 1 {
+                let next = addr + analysis.instructions[addr].size();
+                emitter.line(format!("m.pc = {};", next));
+            }
+        }
+        arms.push("            }".to_owned());
+    }
+
+    let mut code_bits = vec![0u64; proggy.len() / 64 + 1];
+    for addr in &code {
+        code_bits[addr / 64] |= 1 << (addr % 64);
+    }
+    let uses_address = arms.iter().any(|line| line.contains("address("));
+    let mut lines = vec![
+        "// generated by `transpile`, see intcode::transpile. regenerate it rather than editing it"
+            .to_owned(),
+        String::new(),
+        if uses_address {
+            "use super::{address, Compiled, Machine};"
+        } else {
+            "use super::{Compiled, Machine};"
+        }
+        .to_owned(),
+        "use crate::intcode::RunResult;".to_owned(),
+        String::new(),
+        "pub static PROGRAM: Compiled = Compiled {".to_owned(),
+        "    proggy: &[".to_owned(),
+    ];
+    lines.extend(wrapped(proggy.iter().map(|value| value.to_string())));
+    lines.push("    ],".to_owned());
+    lines.push("    code: &[".to_owned());
+    lines.extend(wrapped(code_bits.iter().map(|bits| format!("{:#x}", bits))));
+    lines.push("    ],".to_owned());
+    lines.push("    run,".to_owned());
+    lines.push("};".to_owned());
+    lines.push(String::new());
+    lines.push("fn run(m: &mut Machine) -> RunResult {".to_owned());
+    lines.push("    loop {".to_owned());
+    lines.push("        match m.pc {".to_owned());
+    lines.append(&mut arms);
+    lines.push("            _ => return m.fall_back(m.pc, 0),".to_owned());
+    lines.push("        }".to_owned());
+    lines.push("    }".to_owned());
+    lines.push("}".to_owned());
+    lines.join("\n") + "\n"
+}
+
+// reads x and y, prints x + y and x * y, then adds x * y to the relative base twice. the right x
+// and y make any of those overflow
+#[cfg(test)]
+pub fn overflows_proggy() -> Vec<i128> {
+    vec![
+        3, 21, 3, 22, 1, 21, 22, 23, 4, 23, 2, 21, 22, 24, 4, 24, 9, 24, 9, 24, 99, 0, 0, 0, 0,
+    ]
+}
+
+#[cfg(test)]
+fn run_both(program: &'static Compiled, inputs: &[i128]) -> (Machine, IntCodeComputer) {
+    let mut machine = Machine::new(program);
+    let mut icc = IntCodeComputer::from_ints(program.proggy.to_vec());
+    for &input in inputs {
+        machine.queue_input(input);
+        icc.queue_input(input);
+    }
+    (machine, icc)
+}
+
+#[test]
+fn generated_programs_are_up_to_date() {
+    use crate::intcode::parse_proggy;
+    assert_eq!(
+        include_str!("transpile/day9_boost.rs"),
+        transpile(&parse_proggy(include_str!("../../input/2019/day9.txt")))
+    );
+    assert_eq!(
+        include_str!("transpile/day19_beam.rs"),
+        transpile(&parse_proggy(include_str!("../../input/2019/day19.txt")))
+    );
+    assert_eq!(
+        include_str!("transpile/patches_itself.rs"),
+        transpile(&[109, 5, 203, 0, 104, 0, 99])
+    );
+    assert_eq!(
+        include_str!("transpile/overflows.rs"),
+        transpile(&overflows_proggy())
+    );
+}
+
+#[test]
+fn transpiled_boost_matches_the_interpreter() {
+    for mode in 1..=2 {
+        let (mut machine, mut icc) = run_both(&day9_boost::PROGRAM, &[mode]);
+        assert_eq!(icc.run_until_halt(), machine.run_until_halt());
+        assert_eq!(
+            icc.num_instructions_processed,
+            machine.num_instructions_processed()
+        );
+        assert!(!machine.is_interpreting());
+    }
+}
+
+#[test]
+fn transpiled_day19_matches_the_interpreter() {
+    for (x, y) in (0..50)
+        .step_by(7)
+        .flat_map(|x| (0..50).step_by(3).map(move |y| (x, y)))
+    {
+        let (mut machine, mut icc) = run_both(&day19_beam::PROGRAM, &[]);
+        assert!(matches!(
+            machine.run_and_get_next(),
+            RunResult::NeedMoreInput
+        ));
+        assert!(matches!(icc.run_and_get_next(), RunResult::NeedMoreInput));
+        for input in &[x, y] {
+            machine.queue_input(*input);
+            icc.queue_input(*input);
+        }
+        assert_eq!(icc.run_until_halt(), machine.run_until_halt());
+        assert_eq!(
+            icc.num_instructions_processed,
+            machine.num_instructions_processed()
+        );
+        assert!(!machine.is_interpreting());
+    }
+}
+
+#[test]
+fn falls_back_when_the_program_writes_over_its_code() {
+    let mut machine = Machine::new(&patches_itself::PROGRAM);
+    machine.queue_input(42);
+    assert!(matches!(machine.run_and_get_next(), RunResult::Output(42)));
+    assert!(machine.is_interpreting());
+    assert!(matches!(machine.run_and_get_next(), RunResult::Halt));
+}
@@ -0,0 +1,692 @@
+// generated by `transpile`, see intcode::transpile. regenerate it rather than editing it
+
+use super::{address, Compiled, Machine};
+use crate::intcode::RunResult;
+
+pub static PROGRAM: Compiled = Compiled {
+    proggy: &[
+        109, 424, 203, 1, 21101, 0, 11, 0, 1105, 1, 282, 21101, 18, 0, 0, 1105, 1, 259, 2101,
+        0, 1, 221, 203, 1, 21102, 1, 31, 0, 1105, 1, 282, 21101, 0, 38, 0, 1106, 0, 259, 21002,
+        23, 1, 2, 22101, 0, 1, 3, 21101, 0, 1, 1, 21102, 1, 57, 0, 1106, 0, 303, 2102, 1, 1,
+        222, 21001, 221, 0, 3, 20102, 1, 221, 2, 21101, 0, 259, 1, 21101, 80, 0, 0, 1106, 0,
+        225, 21101, 0, 23, 2, 21102, 91, 1, 0, 1106, 0, 303, 1201, 1, 0, 223, 20101, 0, 222, 4,
+        21101, 0, 259, 3, 21102, 1, 225, 2, 21102, 1, 225, 1, 21102, 1, 118, 0, 1105, 1, 225,
+        20102, 1, 222, 3, 21101, 0, 87, 2, 21101, 133, 0, 0, 1106, 0, 303, 21202, 1, -1, 1,
+        22001, 223, 1, 1, 21101, 0, 148, 0, 1105, 1, 259, 2101, 0, 1, 223, 20102, 1, 221, 4,
+        21002, 222, 1, 3, 21101, 0, 9, 2, 1001, 132, -2, 224, 1002, 224, 2, 224, 1001, 224, 3,
+        224, 1002, 132, -1, 132, 1, 224, 132, 224, 21001, 224, 1, 1, 21102, 1, 195, 0, 106, 0,
+        109, 20207, 1, 223, 2, 21001, 23, 0, 1, 21102, 1, -1, 3, 21101, 0, 214, 0, 1106, 0,
+        303, 22101, 1, 1, 1, 204, 1, 99, 0, 0, 0, 0, 109, 5, 2102, 1, -4, 249, 21201, -3, 0, 1,
+        22101, 0, -2, 2, 21202, -1, 1, 3, 21102, 250, 1, 0, 1106, 0, 225, 21202, 1, 1, -4, 109,
+        -5, 2106, 0, 0, 109, 3, 22107, 0, -2, -1, 21202, -1, 2, -1, 21201, -1, -1, -1, 22202,
+        -1, -2, -2, 109, -3, 2105, 1, 0, 109, 3, 21207, -2, 0, -1, 1206, -1, 294, 104, 0, 99,
+        21202, -2, 1, -2, 109, -3, 2105, 1, 0, 109, 5, 22207, -3, -4, -1, 1206, -1, 346, 22201,
+        -4, -3, -4, 21202, -3, -1, -1, 22201, -4, -1, 2, 21202, 2, -1, -1, 22201, -4, -1, 1,
+        22102, 1, -2, 3, 21102, 1, 343, 0, 1106, 0, 303, 1106, 0, 415, 22207, -2, -3, -1, 1206,
+        -1, 387, 22201, -3, -2, -3, 21202, -2, -1, -1, 22201, -3, -1, 3, 21202, 3, -1, -1,
+        22201, -3, -1, 2, 21201, -4, 0, 1, 21102, 384, 1, 0, 1105, 1, 303, 1106, 0, 415, 21202,
+        -4, -1, -4, 22201, -4, -3, -4, 22202, -3, -2, -2, 22202, -2, -4, -4, 22202, -3, -2, -3,
+        21202, -4, -1, -2, 22201, -3, -2, 1, 21202, 1, 1, -4, 109, -5, 2106, 0, 0,
+    ],
+    code: &[
+        0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffef, 0xfdfffffe1fffffff,
+        0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffff,
+    ],
+    run,
+};
+
+fn run(m: &mut Machine) -> RunResult {
+    loop {
+        match m.pc {
+            0 => {
+                m.num_instructions_processed += 1;
+                // 0: arb  424
+                m.rb = match i128::checked_add(m.rb, 424) { Some(value) => value, None => return m.fall_back(0, 1) };
+                m.pc = 2;
+            }
+            2 => {
+                m.num_instructions_processed += 3;
+                // 2: in   [rb+1]
+                let dst = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(2, 3) };
+                if m.is_code(dst) { return m.fall_back(2, 3) }
+                let input = match m.input.pop_back() {
+                    Some(input) => input,
+                    None => { m.num_instructions_processed -= 3; return RunResult::NeedMoreInput; }
+                };
+                m.memory[dst] = input;
+                // 4: add  0, 11, [rb+0]
+                let dst = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(4, 2) };
+                if m.is_code(dst) { return m.fall_back(4, 2) }
+                let value = 11;
+                m.memory[dst] = value;
+                // 8: jnz  1, 282
+                m.pc = 282;
+            }
+            11 => {
+                m.num_instructions_processed += 2;
+                // 11: add  18, 0, [rb+0]
+                let dst = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(11, 2) };
+                if m.is_code(dst) { return m.fall_back(11, 2) }
+                let value = 18;
+                m.memory[dst] = value;
+                // 15: jnz  1, 259
+                m.pc = 259;
+            }
+            18 => {
+                m.num_instructions_processed += 1;
+                // 18: add  0, [rb+1], [221]
+                let b = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(18, 1) };
+                let value = m.memory[b];
+                m.memory[221] = value;
+                m.pc = 22;
+            }
+            22 => {
+                m.num_instructions_processed += 3;
+                // 22: in   [rb+1]
+                let dst = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(22, 3) };
+                if m.is_code(dst) { return m.fall_back(22, 3) }
+                let input = match m.input.pop_back() {
+                    Some(input) => input,
+                    None => { m.num_instructions_processed -= 3; return RunResult::NeedMoreInput; }
+                };
+                m.memory[dst] = input;
+                // 24: mul  1, 31, [rb+0]
+                let dst = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(24, 2) };
+                if m.is_code(dst) { return m.fall_back(24, 2) }
+                let value = 31;
+                m.memory[dst] = value;
+                // 28: jnz  1, 282
+                m.pc = 282;
+            }
+            31 => {
+                m.num_instructions_processed += 2;
+                // 31: add  0, 38, [rb+0]
+                let dst = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(31, 2) };
+                if m.is_code(dst) { return m.fall_back(31, 2) }
+                let value = 38;
+                m.memory[dst] = value;
+                // 35: jz   0, 259
+                m.pc = 259;
+            }
+            38 => {
+                m.num_instructions_processed += 5;
+                // 38: mul  [23], 1, [rb+2]
+                let dst = match address(m.rb.saturating_add(2)) { Some(addr) => addr, None => return m.fall_back(38, 5) };
+                if m.is_code(dst) { return m.fall_back(38, 5) }
+                let value = m.memory[23];
+                m.memory[dst] = value;
+                // 42: add  0, [rb+1], [rb+3]
+                let b = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(42, 4) };
+                let dst = match address(m.rb.saturating_add(3)) { Some(addr) => addr, None => return m.fall_back(42, 4) };
+                if m.is_code(dst) { return m.fall_back(42, 4) }
+                let value = m.memory[b];
+                m.memory[dst] = value;
+                // 46: add  0, 1, [rb+1]
+                let dst = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(46, 3) };
+                if m.is_code(dst) { return m.fall_back(46, 3) }
+                let value = 1;
+                m.memory[dst] = value;
+                // 50: mul  1, 57, [rb+0]
+                let dst = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(50, 2) };
+                if m.is_code(dst) { return m.fall_back(50, 2) }
+                let value = 57;
+                m.memory[dst] = value;
+                // 54: jz   0, 303
+                m.pc = 303;
+            }
+            57 => {
+                m.num_instructions_processed += 6;
+                // 57: mul  1, [rb+1], [222]
+                let b = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(57, 6) };
+                let value = m.memory[b];
+                m.memory[222] = value;
+                // 61: add  [221], 0, [rb+3]
+                let dst = match address(m.rb.saturating_add(3)) { Some(addr) => addr, None => return m.fall_back(61, 5) };
+                if m.is_code(dst) { return m.fall_back(61, 5) }
+                let value = m.memory[221];
+                m.memory[dst] = value;
+                // 65: mul  1, [221], [rb+2]
+                let dst = match address(m.rb.saturating_add(2)) { Some(addr) => addr, None => return m.fall_back(65, 4) };
+                if m.is_code(dst) { return m.fall_back(65, 4) }
+                let value = m.memory[221];
+                m.memory[dst] = value;
+                // 69: add  0, 259, [rb+1]
+                let dst = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(69, 3) };
+                if m.is_code(dst) { return m.fall_back(69, 3) }
+                let value = 259;
+                m.memory[dst] = value;
+                // 73: add  80, 0, [rb+0]
+                let dst = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(73, 2) };
+                if m.is_code(dst) { return m.fall_back(73, 2) }
+                let value = 80;
+                m.memory[dst] = value;
+                // 77: jz   0, 225
+                m.pc = 225;
+            }
+            80 => {
+                m.num_instructions_processed += 3;
+                // 80: add  0, 23, [rb+2]
+                let dst = match address(m.rb.saturating_add(2)) { Some(addr) => addr, None => return m.fall_back(80, 3) };
+                if m.is_code(dst) { return m.fall_back(80, 3) }
+                let value = 23;
+                m.memory[dst] = value;
+                // 84: mul  91, 1, [rb+0]
+                let dst = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(84, 2) };
+                if m.is_code(dst) { return m.fall_back(84, 2) }
+                let value = 91;
+                m.memory[dst] = value;
+                // 88: jz   0, 303
+                m.pc = 303;
+            }
+            91 => {
+                m.num_instructions_processed += 7;
+                // 91: add  [rb+1], 0, [223]
+                let a = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(91, 7) };
+                let value = m.memory[a];
+                m.memory[223] = value;
+                // 95: add  0, [222], [rb+4]
+                let dst = match address(m.rb.saturating_add(4)) { Some(addr) => addr, None => return m.fall_back(95, 6) };
+                if m.is_code(dst) { return m.fall_back(95, 6) }
+                let value = m.memory[222];
+                m.memory[dst] = value;
+                // 99: add  0, 259, [rb+3]
+                let dst = match address(m.rb.saturating_add(3)) { Some(addr) => addr, None => return m.fall_back(99, 5) };
+                if m.is_code(dst) { return m.fall_back(99, 5) }
+                let value = 259;
+                m.memory[dst] = value;
+                // 103: mul  1, 225, [rb+2]
+                let dst = match address(m.rb.saturating_add(2)) { Some(addr) => addr, None => return m.fall_back(103, 4) };
+                if m.is_code(dst) { return m.fall_back(103, 4) }
+                let value = 225;
+                m.memory[dst] = value;
+                // 107: mul  1, 225, [rb+1]
+                let dst = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(107, 3) };
+                if m.is_code(dst) { return m.fall_back(107, 3) }
+                let value = 225;
+                m.memory[dst] = value;
+                // 111: mul  1, 118, [rb+0]
+                let dst = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(111, 2) };
+                if m.is_code(dst) { return m.fall_back(111, 2) }
+                let value = 118;
+                m.memory[dst] = value;
+                // 115: jnz  1, 225
+                m.pc = 225;
+            }
+            118 => {
+                m.num_instructions_processed += 4;
+                // 118: mul  1, [222], [rb+3]
+                let dst = match address(m.rb.saturating_add(3)) { Some(addr) => addr, None => return m.fall_back(118, 4) };
+                if m.is_code(dst) { return m.fall_back(118, 4) }
+                let value = m.memory[222];
+                m.memory[dst] = value;
+                // 122: add  0, 87, [rb+2]
+                let dst = match address(m.rb.saturating_add(2)) { Some(addr) => addr, None => return m.fall_back(122, 3) };
+                if m.is_code(dst) { return m.fall_back(122, 3) }
+                let value = 87;
+                m.memory[dst] = value;
+                // 126: add  133, 0, [rb+0]
+                let dst = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(126, 2) };
+                if m.is_code(dst) { return m.fall_back(126, 2) }
+                let value = 133;
+                m.memory[dst] = value;
+                // 130: jz   0, 303
+                m.pc = match address(m.memory[132]) { Some(target) => target, None => return m.fall_back(130, 1) };
+            }
+            133 => {
+                m.num_instructions_processed += 4;
+                // 133: mul  [rb+1], -1, [rb+1]
+                let a = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(133, 4) };
+                let dst = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(133, 4) };
+                if m.is_code(dst) { return m.fall_back(133, 4) }
+                let value = match i128::checked_mul(m.memory[a], -1) { Some(value) => value, None => return m.fall_back(133, 4) };
+                m.memory[dst] = value;
+                // 137: add  [223], [rb+1], [rb+1]
+                let b = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(137, 3) };
+                let dst = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(137, 3) };
+                if m.is_code(dst) { return m.fall_back(137, 3) }
+                let value = match i128::checked_add(m.memory[223], m.memory[b]) { Some(value) => value, None => return m.fall_back(137, 3) };
+                m.memory[dst] = value;
+                // 141: add  0, 148, [rb+0]
+                let dst = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(141, 2) };
+                if m.is_code(dst) { return m.fall_back(141, 2) }
+                let value = 148;
+                m.memory[dst] = value;
+                // 145: jnz  1, 259
+                m.pc = 259;
+            }
+            148 => {
+                m.num_instructions_processed += 12;
+                // 148: add  0, [rb+1], [223]
+                let b = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(148, 12) };
+                let value = m.memory[b];
+                m.memory[223] = value;
+                // 152: mul  1, [221], [rb+4]
+                let dst = match address(m.rb.saturating_add(4)) { Some(addr) => addr, None => return m.fall_back(152, 11) };
+                if m.is_code(dst) { return m.fall_back(152, 11) }
+                let value = m.memory[221];
+                m.memory[dst] = value;
+                // 156: mul  [222], 1, [rb+3]
+                let dst = match address(m.rb.saturating_add(3)) { Some(addr) => addr, None => return m.fall_back(156, 10) };
+                if m.is_code(dst) { return m.fall_back(156, 10) }
+                let value = m.memory[222];
+                m.memory[dst] = value;
+                // 160: add  0, 9, [rb+2]
+                let dst = match address(m.rb.saturating_add(2)) { Some(addr) => addr, None => return m.fall_back(160, 9) };
+                if m.is_code(dst) { return m.fall_back(160, 9) }
+                let value = 9;
+                m.memory[dst] = value;
+                // 164: add  [132], -2, [224]
+                let value = match i128::checked_add(m.memory[132], -2) { Some(value) => value, None => return m.fall_back(164, 8) };
+                m.memory[224] = value;
+                // 168: mul  [224], 2, [224]
+                let value = match i128::checked_mul(m.memory[224], 2) { Some(value) => value, None => return m.fall_back(168, 7) };
+                m.memory[224] = value;
+                // 172: add  [224], 3, [224]
+                let value = match i128::checked_add(m.memory[224], 3) { Some(value) => value, None => return m.fall_back(172, 6) };
+                m.memory[224] = value;
+                // 176: mul  [132], -1, [132]
+                let value = match i128::checked_mul(m.memory[132], -1) { Some(value) => value, None => return m.fall_back(176, 5) };
+                m.memory[132] = value;
+                // 180: add  [224], [132], [224]
+                let value = match i128::checked_add(m.memory[224], m.memory[132]) { Some(value) => value, None => return m.fall_back(180, 4) };
+                m.memory[224] = value;
+                // 184: add  [224], 1, [rb+1]
+                let dst = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(184, 3) };
+                if m.is_code(dst) { return m.fall_back(184, 3) }
+                let value = match i128::checked_add(m.memory[224], 1) { Some(value) => value, None => return m.fall_back(184, 3) };
+                m.memory[dst] = value;
+                // 188: mul  1, 195, [rb+0]
+                let dst = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(188, 2) };
+                if m.is_code(dst) { return m.fall_back(188, 2) }
+                let value = 195;
+                m.memory[dst] = value;
+                // 192: jz   0, [109]
+                m.pc = match address(m.memory[109]) { Some(target) => target, None => return m.fall_back(192, 1) };
+            }
+            195 => {
+                m.num_instructions_processed += 5;
+                // 195: lt   [rb+1], [223], [rb+2]
+                let a = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(195, 5) };
+                let dst = match address(m.rb.saturating_add(2)) { Some(addr) => addr, None => return m.fall_back(195, 5) };
+                if m.is_code(dst) { return m.fall_back(195, 5) }
+                let value = (m.memory[a] < m.memory[223]) as i128;
+                m.memory[dst] = value;
+                // 199: add  [23], 0, [rb+1]
+                let dst = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(199, 4) };
+                if m.is_code(dst) { return m.fall_back(199, 4) }
+                let value = m.memory[23];
+                m.memory[dst] = value;
+                // 203: mul  1, -1, [rb+3]
+                let dst = match address(m.rb.saturating_add(3)) { Some(addr) => addr, None => return m.fall_back(203, 3) };
+                if m.is_code(dst) { return m.fall_back(203, 3) }
+                let value = -1;
+                m.memory[dst] = value;
+                // 207: add  0, 214, [rb+0]
+                let dst = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(207, 2) };
+                if m.is_code(dst) { return m.fall_back(207, 2) }
+                let value = 214;
+                m.memory[dst] = value;
+                // 211: jz   0, 303
+                m.pc = 303;
+            }
+            214 => {
+                m.num_instructions_processed += 2;
+                // 214: add  1, [rb+1], [rb+1]
+                let b = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(214, 2) };
+                let dst = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(214, 2) };
+                if m.is_code(dst) { return m.fall_back(214, 2) }
+                let value = match i128::checked_add(1, m.memory[b]) { Some(value) => value, None => return m.fall_back(214, 2) };
+                m.memory[dst] = value;
+                // 218: out  [rb+1]
+                let a = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(218, 1) };
+                let value = m.memory[a];
+                m.pc = 220;
+                return RunResult::Output(value);
+            }
+            220 => {
+                m.num_instructions_processed += 1;
+                // 220: halt
+                m.pc = 220;
+                return RunResult::Halt;
+            }
+            225 => {
+                m.num_instructions_processed += 7;
+                // 225: arb  5
+                m.rb = match i128::checked_add(m.rb, 5) { Some(value) => value, None => return m.fall_back(225, 7) };
+                // 227: mul  1, [rb-4], [249]
+                let b = match address(m.rb.saturating_add(-4)) { Some(addr) => addr, None => return m.fall_back(227, 6) };
+                let value = m.memory[b];
+                m.memory[249] = value;
+                // 231: add  [rb-3], 0, [rb+1]
+                let a = match address(m.rb.saturating_add(-3)) { Some(addr) => addr, None => return m.fall_back(231, 5) };
+                let dst = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(231, 5) };
+                if m.is_code(dst) { return m.fall_back(231, 5) }
+                let value = m.memory[a];
+                m.memory[dst] = value;
+                // 235: add  0, [rb-2], [rb+2]
+                let b = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(235, 4) };
+                let dst = match address(m.rb.saturating_add(2)) { Some(addr) => addr, None => return m.fall_back(235, 4) };
+                if m.is_code(dst) { return m.fall_back(235, 4) }
+                let value = m.memory[b];
+                m.memory[dst] = value;
+                // 239: mul  [rb-1], 1, [rb+3]
+                let a = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(239, 3) };
+                let dst = match address(m.rb.saturating_add(3)) { Some(addr) => addr, None => return m.fall_back(239, 3) };
+                if m.is_code(dst) { return m.fall_back(239, 3) }
+                let value = m.memory[a];
+                m.memory[dst] = value;
+                // 243: mul  250, 1, [rb+0]
+                let dst = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(243, 2) };
+                if m.is_code(dst) { return m.fall_back(243, 2) }
+                let value = 250;
+                m.memory[dst] = value;
+                // 247: jz   0, 225
+                m.pc = match address(m.memory[249]) { Some(target) => target, None => return m.fall_back(247, 1) };
+            }
+            250 => {
+                m.num_instructions_processed += 3;
+                // 250: mul  [rb+1], 1, [rb-4]
+                let a = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(250, 3) };
+                let dst = match address(m.rb.saturating_add(-4)) { Some(addr) => addr, None => return m.fall_back(250, 3) };
+                if m.is_code(dst) { return m.fall_back(250, 3) }
+                let value = m.memory[a];
+                m.memory[dst] = value;
+                // 254: arb  -5
+                m.rb = match i128::checked_add(m.rb, -5) { Some(value) => value, None => return m.fall_back(254, 2) };
+                // 256: jz   0, [rb+0]
+                let b = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(256, 1) };
+                m.pc = match address(m.memory[b]) { Some(target) => target, None => return m.fall_back(256, 1) };
+            }
+            259 => {
+                m.num_instructions_processed += 7;
+                // 259: arb  3
+                m.rb = match i128::checked_add(m.rb, 3) { Some(value) => value, None => return m.fall_back(259, 7) };
+                // 261: lt   0, [rb-2], [rb-1]
+                let b = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(261, 6) };
+                let dst = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(261, 6) };
+                if m.is_code(dst) { return m.fall_back(261, 6) }
+                let value = (0 < m.memory[b]) as i128;
+                m.memory[dst] = value;
+                // 265: mul  [rb-1], 2, [rb-1]
+                let a = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(265, 5) };
+                let dst = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(265, 5) };
+                if m.is_code(dst) { return m.fall_back(265, 5) }
+                let value = match i128::checked_mul(m.memory[a], 2) { Some(value) => value, None => return m.fall_back(265, 5) };
+                m.memory[dst] = value;
+                // 269: add  [rb-1], -1, [rb-1]
+                let a = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(269, 4) };
+                let dst = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(269, 4) };
+                if m.is_code(dst) { return m.fall_back(269, 4) }
+                let value = match i128::checked_add(m.memory[a], -1) { Some(value) => value, None => return m.fall_back(269, 4) };
+                m.memory[dst] = value;
+                // 273: mul  [rb-1], [rb-2], [rb-2]
+                let a = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(273, 3) };
+                let b = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(273, 3) };
+                let dst = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(273, 3) };
+                if m.is_code(dst) { return m.fall_back(273, 3) }
+                let value = match i128::checked_mul(m.memory[a], m.memory[b]) { Some(value) => value, None => return m.fall_back(273, 3) };
+                m.memory[dst] = value;
+                // 277: arb  -3
+                m.rb = match i128::checked_add(m.rb, -3) { Some(value) => value, None => return m.fall_back(277, 2) };
+                // 279: jnz  1, [rb+0]
+                let b = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(279, 1) };
+                m.pc = match address(m.memory[b]) { Some(target) => target, None => return m.fall_back(279, 1) };
+            }
+            282 => {
+                m.num_instructions_processed += 3;
+                // 282: arb  3
+                m.rb = match i128::checked_add(m.rb, 3) { Some(value) => value, None => return m.fall_back(282, 3) };
+                // 284: lt   [rb-2], 0, [rb-1]
+                let a = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(284, 2) };
+                let dst = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(284, 2) };
+                if m.is_code(dst) { return m.fall_back(284, 2) }
+                let value = (m.memory[a] < 0) as i128;
+                m.memory[dst] = value;
+                // 288: jz   [rb-1], 294
+                let a = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(288, 1) };
+                if m.memory[a] == 0 {
+                    m.pc = 294;
+                } else {
+                    m.pc = 291;
+                }
+            }
+            291 => {
+                m.num_instructions_processed += 1;
+                // 291: out  0
+                let value = 0;
+                m.pc = 293;
+                return RunResult::Output(value);
+            }
+            293 => {
+                m.num_instructions_processed += 1;
+                // 293: halt
+                m.pc = 293;
+                return RunResult::Halt;
+            }
+            294 => {
+                m.num_instructions_processed += 3;
+                // 294: mul  [rb-2], 1, [rb-2]
+                let a = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(294, 3) };
+                let dst = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(294, 3) };
+                if m.is_code(dst) { return m.fall_back(294, 3) }
+                let value = m.memory[a];
+                m.memory[dst] = value;
+                // 298: arb  -3
+                m.rb = match i128::checked_add(m.rb, -3) { Some(value) => value, None => return m.fall_back(298, 2) };
+                // 300: jnz  1, [rb+0]
+                let b = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(300, 1) };
+                m.pc = match address(m.memory[b]) { Some(target) => target, None => return m.fall_back(300, 1) };
+            }
+            303 => {
+                m.num_instructions_processed += 3;
+                // 303: arb  5
+                m.rb = match i128::checked_add(m.rb, 5) { Some(value) => value, None => return m.fall_back(303, 3) };
+                // 305: lt   [rb-3], [rb-4], [rb-1]
+                let a = match address(m.rb.saturating_add(-3)) { Some(addr) => addr, None => return m.fall_back(305, 2) };
+                let b = match address(m.rb.saturating_add(-4)) { Some(addr) => addr, None => return m.fall_back(305, 2) };
+                let dst = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(305, 2) };
+                if m.is_code(dst) { return m.fall_back(305, 2) }
+                let value = (m.memory[a] < m.memory[b]) as i128;
+                m.memory[dst] = value;
+                // 309: jz   [rb-1], 346
+                let a = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(309, 1) };
+                if m.memory[a] == 0 {
+                    m.pc = 346;
+                } else {
+                    m.pc = 312;
+                }
+            }
+            312 => {
+                m.num_instructions_processed += 8;
+                // 312: add  [rb-4], [rb-3], [rb-4]
+                let a = match address(m.rb.saturating_add(-4)) { Some(addr) => addr, None => return m.fall_back(312, 8) };
+                let b = match address(m.rb.saturating_add(-3)) { Some(addr) => addr, None => return m.fall_back(312, 8) };
+                let dst = match address(m.rb.saturating_add(-4)) { Some(addr) => addr, None => return m.fall_back(312, 8) };
+                if m.is_code(dst) { return m.fall_back(312, 8) }
+                let value = match i128::checked_add(m.memory[a], m.memory[b]) { Some(value) => value, None => return m.fall_back(312, 8) };
+                m.memory[dst] = value;
+                // 316: mul  [rb-3], -1, [rb-1]
+                let a = match address(m.rb.saturating_add(-3)) { Some(addr) => addr, None => return m.fall_back(316, 7) };
+                let dst = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(316, 7) };
+                if m.is_code(dst) { return m.fall_back(316, 7) }
+                let value = match i128::checked_mul(m.memory[a], -1) { Some(value) => value, None => return m.fall_back(316, 7) };
+                m.memory[dst] = value;
+                // 320: add  [rb-4], [rb-1], [rb+2]
+                let a = match address(m.rb.saturating_add(-4)) { Some(addr) => addr, None => return m.fall_back(320, 6) };
+                let b = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(320, 6) };
+                let dst = match address(m.rb.saturating_add(2)) { Some(addr) => addr, None => return m.fall_back(320, 6) };
+                if m.is_code(dst) { return m.fall_back(320, 6) }
+                let value = match i128::checked_add(m.memory[a], m.memory[b]) { Some(value) => value, None => return m.fall_back(320, 6) };
+                m.memory[dst] = value;
+                // 324: mul  [rb+2], -1, [rb-1]
+                let a = match address(m.rb.saturating_add(2)) { Some(addr) => addr, None => return m.fall_back(324, 5) };
+                let dst = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(324, 5) };
+                if m.is_code(dst) { return m.fall_back(324, 5) }
+                let value = match i128::checked_mul(m.memory[a], -1) { Some(value) => value, None => return m.fall_back(324, 5) };
+                m.memory[dst] = value;
+                // 328: add  [rb-4], [rb-1], [rb+1]
+                let a = match address(m.rb.saturating_add(-4)) { Some(addr) => addr, None => return m.fall_back(328, 4) };
+                let b = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(328, 4) };
+                let dst = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(328, 4) };
+                if m.is_code(dst) { return m.fall_back(328, 4) }
+                let value = match i128::checked_add(m.memory[a], m.memory[b]) { Some(value) => value, None => return m.fall_back(328, 4) };
+                m.memory[dst] = value;
+                // 332: mul  1, [rb-2], [rb+3]
+                let b = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(332, 3) };
+                let dst = match address(m.rb.saturating_add(3)) { Some(addr) => addr, None => return m.fall_back(332, 3) };
+                if m.is_code(dst) { return m.fall_back(332, 3) }
+                let value = m.memory[b];
+                m.memory[dst] = value;
+                // 336: mul  1, 343, [rb+0]
+                let dst = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(336, 2) };
+                if m.is_code(dst) { return m.fall_back(336, 2) }
+                let value = 343;
+                m.memory[dst] = value;
+                // 340: jz   0, 303
+                m.pc = 303;
+            }
+            343 => {
+                m.num_instructions_processed += 1;
+                // 343: jz   0, 415
+                m.pc = 415;
+            }
+            346 => {
+                m.num_instructions_processed += 2;
+                // 346: lt   [rb-2], [rb-3], [rb-1]
+                let a = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(346, 2) };
+                let b = match address(m.rb.saturating_add(-3)) { Some(addr) => addr, None => return m.fall_back(346, 2) };
+                let dst = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(346, 2) };
+                if m.is_code(dst) { return m.fall_back(346, 2) }
+                let value = (m.memory[a] < m.memory[b]) as i128;
+                m.memory[dst] = value;
+                // 350: jz   [rb-1], 387
+                let a = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(350, 1) };
+                if m.memory[a] == 0 {
+                    m.pc = 387;
+                } else {
+                    m.pc = 353;
+                }
+            }
+            353 => {
+                m.num_instructions_processed += 8;
+                // 353: add  [rb-3], [rb-2], [rb-3]
+                let a = match address(m.rb.saturating_add(-3)) { Some(addr) => addr, None => return m.fall_back(353, 8) };
+                let b = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(353, 8) };
+                let dst = match address(m.rb.saturating_add(-3)) { Some(addr) => addr, None => return m.fall_back(353, 8) };
+                if m.is_code(dst) { return m.fall_back(353, 8) }
+                let value = match i128::checked_add(m.memory[a], m.memory[b]) { Some(value) => value, None => return m.fall_back(353, 8) };
+                m.memory[dst] = value;
+                // 357: mul  [rb-2], -1, [rb-1]
+                let a = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(357, 7) };
+                let dst = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(357, 7) };
+                if m.is_code(dst) { return m.fall_back(357, 7) }
+                let value = match i128::checked_mul(m.memory[a], -1) { Some(value) => value, None => return m.fall_back(357, 7) };
+                m.memory[dst] = value;
+                // 361: add  [rb-3], [rb-1], [rb+3]
+                let a = match address(m.rb.saturating_add(-3)) { Some(addr) => addr, None => return m.fall_back(361, 6) };
+                let b = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(361, 6) };
+                let dst = match address(m.rb.saturating_add(3)) { Some(addr) => addr, None => return m.fall_back(361, 6) };
+                if m.is_code(dst) { return m.fall_back(361, 6) }
+                let value = match i128::checked_add(m.memory[a], m.memory[b]) { Some(value) => value, None => return m.fall_back(361, 6) };
+                m.memory[dst] = value;
+                // 365: mul  [rb+3], -1, [rb-1]
+                let a = match address(m.rb.saturating_add(3)) { Some(addr) => addr, None => return m.fall_back(365, 5) };
+                let dst = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(365, 5) };
+                if m.is_code(dst) { return m.fall_back(365, 5) }
+                let value = match i128::checked_mul(m.memory[a], -1) { Some(value) => value, None => return m.fall_back(365, 5) };
+                m.memory[dst] = value;
+                // 369: add  [rb-3], [rb-1], [rb+2]
+                let a = match address(m.rb.saturating_add(-3)) { Some(addr) => addr, None => return m.fall_back(369, 4) };
+                let b = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(369, 4) };
+                let dst = match address(m.rb.saturating_add(2)) { Some(addr) => addr, None => return m.fall_back(369, 4) };
+                if m.is_code(dst) { return m.fall_back(369, 4) }
+                let value = match i128::checked_add(m.memory[a], m.memory[b]) { Some(value) => value, None => return m.fall_back(369, 4) };
+                m.memory[dst] = value;
+                // 373: add  [rb-4], 0, [rb+1]
+                let a = match address(m.rb.saturating_add(-4)) { Some(addr) => addr, None => return m.fall_back(373, 3) };
+                let dst = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(373, 3) };
+                if m.is_code(dst) { return m.fall_back(373, 3) }
+                let value = m.memory[a];
+                m.memory[dst] = value;
+                // 377: mul  384, 1, [rb+0]
+                let dst = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(377, 2) };
+                if m.is_code(dst) { return m.fall_back(377, 2) }
+                let value = 384;
+                m.memory[dst] = value;
+                // 381: jnz  1, 303
+                m.pc = 303;
+            }
+            384 => {
+                m.num_instructions_processed += 1;
+                // 384: jz   0, 415
+                m.pc = 415;
+            }
+            387 => {
+                m.num_instructions_processed += 7;
+                // 387: mul  [rb-4], -1, [rb-4]
+                let a = match address(m.rb.saturating_add(-4)) { Some(addr) => addr, None => return m.fall_back(387, 7) };
+                let dst = match address(m.rb.saturating_add(-4)) { Some(addr) => addr, None => return m.fall_back(387, 7) };
+                if m.is_code(dst) { return m.fall_back(387, 7) }
+                let value = match i128::checked_mul(m.memory[a], -1) { Some(value) => value, None => return m.fall_back(387, 7) };
+                m.memory[dst] = value;
+                // 391: add  [rb-4], [rb-3], [rb-4]
+                let a = match address(m.rb.saturating_add(-4)) { Some(addr) => addr, None => return m.fall_back(391, 6) };
+                let b = match address(m.rb.saturating_add(-3)) { Some(addr) => addr, None => return m.fall_back(391, 6) };
+                let dst = match address(m.rb.saturating_add(-4)) { Some(addr) => addr, None => return m.fall_back(391, 6) };
+                if m.is_code(dst) { return m.fall_back(391, 6) }
+                let value = match i128::checked_add(m.memory[a], m.memory[b]) { Some(value) => value, None => return m.fall_back(391, 6) };
+                m.memory[dst] = value;
+                // 395: mul  [rb-3], [rb-2], [rb-2]
+                let a = match address(m.rb.saturating_add(-3)) { Some(addr) => addr, None => return m.fall_back(395, 5) };
+                let b = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(395, 5) };
+                let dst = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(395, 5) };
+                if m.is_code(dst) { return m.fall_back(395, 5) }
+                let value = match i128::checked_mul(m.memory[a], m.memory[b]) { Some(value) => value, None => return m.fall_back(395, 5) };
+                m.memory[dst] = value;
+                // 399: mul  [rb-2], [rb-4], [rb-4]
+                let a = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(399, 4) };
+                let b = match address(m.rb.saturating_add(-4)) { Some(addr) => addr, None => return m.fall_back(399, 4) };
+                let dst = match address(m.rb.saturating_add(-4)) { Some(addr) => addr, None => return m.fall_back(399, 4) };
+                if m.is_code(dst) { return m.fall_back(399, 4) }
+                let value = match i128::checked_mul(m.memory[a], m.memory[b]) { Some(value) => value, None => return m.fall_back(399, 4) };
+                m.memory[dst] = value;
+                // 403: mul  [rb-3], [rb-2], [rb-3]
+                let a = match address(m.rb.saturating_add(-3)) { Some(addr) => addr, None => return m.fall_back(403, 3) };
+                let b = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(403, 3) };
+                let dst = match address(m.rb.saturating_add(-3)) { Some(addr) => addr, None => return m.fall_back(403, 3) };
+                if m.is_code(dst) { return m.fall_back(403, 3) }
+                let value = match i128::checked_mul(m.memory[a], m.memory[b]) { Some(value) => value, None => return m.fall_back(403, 3) };
+                m.memory[dst] = value;
+                // 407: mul  [rb-4], -1, [rb-2]
+                let a = match address(m.rb.saturating_add(-4)) { Some(addr) => addr, None => return m.fall_back(407, 2) };
+                let dst = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(407, 2) };
+                if m.is_code(dst) { return m.fall_back(407, 2) }
+                let value = match i128::checked_mul(m.memory[a], -1) { Some(value) => value, None => return m.fall_back(407, 2) };
+                m.memory[dst] = value;
+                // 411: add  [rb-3], [rb-2], [rb+1]
+                let a = match address(m.rb.saturating_add(-3)) { Some(addr) => addr, None => return m.fall_back(411, 1) };
+                let b = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(411, 1) };
+                let dst = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(411, 1) };
+                if m.is_code(dst) { return m.fall_back(411, 1) }
+                let value = match i128::checked_add(m.memory[a], m.memory[b]) { Some(value) => value, None => return m.fall_back(411, 1) };
+                m.memory[dst] = value;
+                m.pc = 415;
+            }
+            415 => {
+                m.num_instructions_processed += 3;
+                // 415: mul  [rb+1], 1, [rb-4]
+                let a = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(415, 3) };
+                let dst = match address(m.rb.saturating_add(-4)) { Some(addr) => addr, None => return m.fall_back(415, 3) };
+                if m.is_code(dst) { return m.fall_back(415, 3) }
+                let value = m.memory[a];
+                m.memory[dst] = value;
+                // 419: arb  -5
+                m.rb = match i128::checked_add(m.rb, -5) { Some(value) => value, None => return m.fall_back(419, 2) };
+                // 421: jz   0, [rb+0]
+                let b = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(421, 1) };
+                m.pc = match address(m.memory[b]) { Some(target) => target, None => return m.fall_back(421, 1) };
+            }
+            _ => return m.fall_back(m.pc, 0),
+        }
+    }
+}
@@ -0,0 +1,1556 @@
+// generated by `transpile`, see intcode::transpile. regenerate it rather than editing it
+
+use super::{address, Compiled, Machine};
+use crate::intcode::RunResult;
+
+pub static PROGRAM: Compiled = Compiled {
+    proggy: &[
+        1102, 34463338, 34463338, 63, 1007, 63, 34463338, 63, 1005, 63, 53, 1102, 3, 1, 1000,
+        109, 988, 209, 12, 9, 1000, 209, 6, 209, 3, 203, 0, 1008, 1000, 1, 63, 1005, 63, 65,
+        1008, 1000, 2, 63, 1005, 63, 904, 1008, 1000, 0, 63, 1005, 63, 58, 4, 25, 104, 0, 99,
+        4, 0, 104, 0, 99, 4, 17, 104, 0, 99, 0, 0, 1101, 0, 26, 1014, 1102, 1, 30, 1013, 1101,
+        22, 0, 1000, 1101, 0, 35, 1015, 1101, 0, 34, 1011, 1102, 0, 1, 1020, 1102, 1, 481,
+        1022, 1101, 0, 36, 1003, 1102, 1, 28, 1005, 1101, 857, 0, 1024, 1101, 20, 0, 1008,
+        1101, 0, 385, 1026, 1102, 37, 1, 1006, 1101, 33, 0, 1017, 1101, 0, 38, 1002, 1102, 23,
+        1, 1007, 1102, 32, 1, 1010, 1101, 29, 0, 1016, 1102, 1, 25, 1009, 1102, 1, 27, 1012,
+        1101, 24, 0, 1018, 1101, 474, 0, 1023, 1102, 1, 39, 1004, 1101, 0, 31, 1001, 1102, 378,
+        1, 1027, 1101, 0, 848, 1025, 1102, 21, 1, 1019, 1102, 760, 1, 1029, 1102, 1, 1, 1021,
+        1101, 769, 0, 1028, 109, -6, 2107, 21, 6, 63, 1005, 63, 199, 4, 187, 1106, 0, 203,
+        1001, 64, 1, 64, 1002, 64, 2, 64, 109, 16, 2101, 0, -6, 63, 1008, 63, 39, 63, 1005, 63,
+        225, 4, 209, 1106, 0, 229, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 5, 2108, 20, -7, 63,
+        1005, 63, 247, 4, 235, 1105, 1, 251, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -1, 2108,
+        36, -8, 63, 1005, 63, 267, 1106, 0, 273, 4, 257, 1001, 64, 1, 64, 1002, 64, 2, 64, 109,
+        -13, 1201, -1, 0, 63, 1008, 63, 22, 63, 1005, 63, 299, 4, 279, 1001, 64, 1, 64, 1106,
+        0, 299, 1002, 64, 2, 64, 109, 15, 2102, 1, -8, 63, 1008, 63, 20, 63, 1005, 63, 321, 4,
+        305, 1106, 0, 325, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -13, 21108, 40, 40, 8, 1005,
+        1011, 347, 4, 331, 1001, 64, 1, 64, 1105, 1, 347, 1002, 64, 2, 64, 109, -2, 1207, 8,
+        24, 63, 1005, 63, 363, 1105, 1, 369, 4, 353, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 35,
+        2106, 0, -9, 1001, 64, 1, 64, 1106, 0, 387, 4, 375, 1002, 64, 2, 64, 109, -26, 21102,
+        41, 1, 3, 1008, 1013, 41, 63, 1005, 63, 409, 4, 393, 1106, 0, 413, 1001, 64, 1, 64,
+        1002, 64, 2, 64, 109, 2, 1202, -6, 1, 63, 1008, 63, 36, 63, 1005, 63, 433, 1106, 0,
+        439, 4, 419, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -3, 21102, 42, 1, 10, 1008, 1019,
+        40, 63, 1005, 63, 463, 1001, 64, 1, 64, 1106, 0, 465, 4, 445, 1002, 64, 2, 64, 109, 15,
+        2105, 1, -1, 1001, 64, 1, 64, 1106, 0, 483, 4, 471, 1002, 64, 2, 64, 109, -27, 1207, 3,
+        23, 63, 1005, 63, 505, 4, 489, 1001, 64, 1, 64, 1105, 1, 505, 1002, 64, 2, 64, 109, 13,
+        2102, 1, -9, 63, 1008, 63, 28, 63, 1005, 63, 525, 1105, 1, 531, 4, 511, 1001, 64, 1,
+        64, 1002, 64, 2, 64, 109, 1, 2101, 0, -8, 63, 1008, 63, 35, 63, 1005, 63, 551, 1105, 1,
+        557, 4, 537, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 6, 21107, 43, 44, -4, 1005, 1013,
+        575, 4, 563, 1106, 0, 579, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -9, 1201, -4, 0, 63,
+        1008, 63, 40, 63, 1005, 63, 599, 1105, 1, 605, 4, 585, 1001, 64, 1, 64, 1002, 64, 2,
+        64, 109, 12, 1206, 1, 621, 1001, 64, 1, 64, 1106, 0, 623, 4, 611, 1002, 64, 2, 64, 109,
+        -22, 1202, 9, 1, 63, 1008, 63, 23, 63, 1005, 63, 649, 4, 629, 1001, 64, 1, 64, 1105, 1,
+        649, 1002, 64, 2, 64, 109, 17, 1206, 5, 667, 4, 655, 1001, 64, 1, 64, 1106, 0, 667,
+        1002, 64, 2, 64, 109, -3, 1205, 9, 685, 4, 673, 1001, 64, 1, 64, 1106, 0, 685, 1002,
+        64, 2, 64, 109, 3, 1208, -9, 37, 63, 1005, 63, 707, 4, 691, 1001, 64, 1, 64, 1105, 1,
+        707, 1002, 64, 2, 64, 109, 7, 1205, -2, 723, 1001, 64, 1, 64, 1106, 0, 725, 4, 713,
+        1002, 64, 2, 64, 109, -15, 21101, 44, 0, 8, 1008, 1015, 45, 63, 1005, 63, 745, 1105, 1,
+        751, 4, 731, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 28, 2106, 0, -7, 4, 757, 1001, 64,
+        1, 64, 1106, 0, 769, 1002, 64, 2, 64, 109, -12, 21101, 45, 0, -5, 1008, 1018, 45, 63,
+        1005, 63, 791, 4, 775, 1105, 1, 795, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -9, 2107,
+        26, -5, 63, 1005, 63, 815, 1001, 64, 1, 64, 1106, 0, 817, 4, 801, 1002, 64, 2, 64, 109,
+        -1, 21107, 46, 45, -3, 1005, 1010, 833, 1105, 1, 839, 4, 823, 1001, 64, 1, 64, 1002,
+        64, 2, 64, 109, 3, 2105, 1, 8, 4, 845, 1001, 64, 1, 64, 1106, 0, 857, 1002, 64, 2, 64,
+        109, -9, 1208, -4, 37, 63, 1005, 63, 877, 1001, 64, 1, 64, 1105, 1, 879, 4, 863, 1002,
+        64, 2, 64, 109, 8, 21108, 47, 46, 2, 1005, 1017, 895, 1106, 0, 901, 4, 885, 1001, 64,
+        1, 64, 4, 64, 99, 21102, 1, 27, 1, 21102, 1, 915, 0, 1106, 0, 922, 21201, 1, 14429, 1,
+        204, 1, 99, 109, 3, 1207, -2, 3, 63, 1005, 63, 964, 21201, -2, -1, 1, 21102, 1, 942, 0,
+        1105, 1, 922, 21202, 1, 1, -1, 21201, -2, -3, 1, 21101, 957, 0, 0, 1106, 0, 922, 22201,
+        1, -1, -2, 1105, 1, 968, 21201, -2, 0, -2, 109, -3, 2105, 1, 0,
+    ],
+    code: &[
+        0x7fffffffffffffff, 0xfffffffffffffffe, 0xffffffffffffffff, 0xffffffffffffffff,
+        0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff,
+        0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff,
+        0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff, 0x1fff,
+    ],
+    run,
+};
+
+fn run(m: &mut Machine) -> RunResult {
+    loop {
+        match m.pc {
+            0 => {
+                m.num_instructions_processed += 3;
+                // 0: mul  34463338, 34463338, [63]
+                let value = 1187721666102244;
+                m.memory[63] = value;
+                // 4: lt   [63], 34463338, [63]
+                let value = (m.memory[63] < 34463338) as i128;
+                m.memory[63] = value;
+                // 8: jnz  [63], 53
+                if m.memory[63] != 0 {
+                    m.pc = 53;
+                } else {
+                    m.pc = 11;
+                }
+            }
+            11 => {
+                m.num_instructions_processed += 6;
+                // 11: mul  3, 1, [1000]
+                let value = 3;
+                m.memory[1000] = value;
+                // 15: arb  988
+                m.rb = match i128::checked_add(m.rb, 988) { Some(value) => value, None => return m.fall_back(15, 5) };
+                // 17: arb  [rb+12]
+                let a = match address(m.rb.saturating_add(12)) { Some(addr) => addr, None => return m.fall_back(17, 4) };
+                m.rb = match i128::checked_add(m.rb, m.memory[a]) { Some(value) => value, None => return m.fall_back(17, 4) };
+                // 19: arb  [1000]
+                m.rb = match i128::checked_add(m.rb, m.memory[1000]) { Some(value) => value, None => return m.fall_back(19, 3) };
+                // 21: arb  [rb+6]
+                let a = match address(m.rb.saturating_add(6)) { Some(addr) => addr, None => return m.fall_back(21, 2) };
+                m.rb = match i128::checked_add(m.rb, m.memory[a]) { Some(value) => value, None => return m.fall_back(21, 2) };
+                // 23: arb  [rb+3]
+                let a = match address(m.rb.saturating_add(3)) { Some(addr) => addr, None => return m.fall_back(23, 1) };
+                m.rb = match i128::checked_add(m.rb, m.memory[a]) { Some(value) => value, None => return m.fall_back(23, 1) };
+                m.pc = 25;
+            }
+            25 => {
+                m.num_instructions_processed += 3;
+                // 25: in   [rb+0]
+                let dst = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(25, 3) };
+                if m.is_code(dst) { return m.fall_back(25, 3) }
+                let input = match m.input.pop_back() {
+                    Some(input) => input,
+                    None => { m.num_instructions_processed -= 3; return RunResult::NeedMoreInput; }
+                };
+                m.memory[dst] = input;
+                // 27: eq   [1000], 1, [63]
+                let value = (m.memory[1000] == 1) as i128;
+                m.memory[63] = value;
+                // 31: jnz  [63], 65
+                if m.memory[63] != 0 {
+                    m.pc = 65;
+                } else {
+                    m.pc = 34;
+                }
+            }
+            34 => {
+                m.num_instructions_processed += 2;
+                // 34: eq   [1000], 2, [63]
+                let value = (m.memory[1000] == 2) as i128;
+                m.memory[63] = value;
+                // 38: jnz  [63], 904
+                if m.memory[63] != 0 {
+                    m.pc = 904;
+                } else {
+                    m.pc = 41;
+                }
+            }
+            41 => {
+                m.num_instructions_processed += 2;
+                // 41: eq   [1000], 0, [63]
+                let value = (m.memory[1000] == 0) as i128;
+                m.memory[63] = value;
+                // 45: jnz  [63], 58
+                if m.memory[63] != 0 {
+                    m.pc = 58;
+                } else {
+                    m.pc = 48;
+                }
+            }
+            48 => {
+                m.num_instructions_processed += 1;
+                // 48: out  [25]
+                let value = m.memory[25];
+                m.pc = 50;
+                return RunResult::Output(value);
+            }
+            50 => {
+                m.num_instructions_processed += 1;
+                // 50: out  0
+                let value = 0;
+                m.pc = 52;
+                return RunResult::Output(value);
+            }
+            52 => {
+                m.num_instructions_processed += 1;
+                // 52: halt
+                m.pc = 52;
+                return RunResult::Halt;
+            }
+            53 => {
+                m.num_instructions_processed += 1;
+                // 53: out  [0]
+                let value = m.memory[0];
+                m.pc = 55;
+                return RunResult::Output(value);
+            }
+            55 => {
+                m.num_instructions_processed += 1;
+                // 55: out  0
+                let value = 0;
+                m.pc = 57;
+                return RunResult::Output(value);
+            }
+            57 => {
+                m.num_instructions_processed += 1;
+                // 57: halt
+                m.pc = 57;
+                return RunResult::Halt;
+            }
+            58 => {
+                m.num_instructions_processed += 1;
+                // 58: out  [17]
+                let value = m.memory[17];
+                m.pc = 60;
+                return RunResult::Output(value);
+            }
+            60 => {
+                m.num_instructions_processed += 1;
+                // 60: out  0
+                let value = 0;
+                m.pc = 62;
+                return RunResult::Output(value);
+            }
+            62 => {
+                m.num_instructions_processed += 1;
+                // 62: halt
+                m.pc = 62;
+                return RunResult::Halt;
+            }
+            65 => {
+                m.num_instructions_processed += 33;
+                // 65: add  0, 26, [1014]
+                let value = 26;
+                m.memory[1014] = value;
+                // 69: mul  1, 30, [1013]
+                let value = 30;
+                m.memory[1013] = value;
+                // 73: add  22, 0, [1000]
+                let value = 22;
+                m.memory[1000] = value;
+                // 77: add  0, 35, [1015]
+                let value = 35;
+                m.memory[1015] = value;
+                // 81: add  0, 34, [1011]
+                let value = 34;
+                m.memory[1011] = value;
+                // 85: mul  0, 1, [1020]
+                let value = 0;
+                m.memory[1020] = value;
+                // 89: mul  1, 481, [1022]
+                let value = 481;
+                m.memory[1022] = value;
+                // 93: add  0, 36, [1003]
+                let value = 36;
+                m.memory[1003] = value;
+                // 97: mul  1, 28, [1005]
+                let value = 28;
+                m.memory[1005] = value;
+                // 101: add  857, 0, [1024]
+                let value = 857;
+                m.memory[1024] = value;
+                // 105: add  20, 0, [1008]
+                let value = 20;
+                m.memory[1008] = value;
+                // 109: add  0, 385, [1026]
+                let value = 385;
+                m.memory[1026] = value;
+                // 113: mul  37, 1, [1006]
+                let value = 37;
+                m.memory[1006] = value;
+                // 117: add  33, 0, [1017]
+                let value = 33;
+                m.memory[1017] = value;
+                // 121: add  0, 38, [1002]
+                let value = 38;
+                m.memory[1002] = value;
+                // 125: mul  23, 1, [1007]
+                let value = 23;
+                m.memory[1007] = value;
+                // 129: mul  32, 1, [1010]
+                let value = 32;
+                m.memory[1010] = value;
+                // 133: add  29, 0, [1016]
+                let value = 29;
+                m.memory[1016] = value;
+                // 137: mul  1, 25, [1009]
+                let value = 25;
+                m.memory[1009] = value;
+                // 141: mul  1, 27, [1012]
+                let value = 27;
+                m.memory[1012] = value;
+                // 145: add  24, 0, [1018]
+                let value = 24;
+                m.memory[1018] = value;
+                // 149: add  474, 0, [1023]
+                let value = 474;
+                m.memory[1023] = value;
+                // 153: mul  1, 39, [1004]
+                let value = 39;
+                m.memory[1004] = value;
+                // 157: add  0, 31, [1001]
+                let value = 31;
+                m.memory[1001] = value;
+                // 161: mul  378, 1, [1027]
+                let value = 378;
+                m.memory[1027] = value;
+                // 165: add  0, 848, [1025]
+                let value = 848;
+                m.memory[1025] = value;
+                // 169: mul  21, 1, [1019]
+                let value = 21;
+                m.memory[1019] = value;
+                // 173: mul  760, 1, [1029]
+                let value = 760;
+                m.memory[1029] = value;
+                // 177: mul  1, 1, [1021]
+                let value = 1;
+                m.memory[1021] = value;
+                // 181: add  769, 0, [1028]
+                let value = 769;
+                m.memory[1028] = value;
+                // 185: arb  -6
+                m.rb = match i128::checked_add(m.rb, -6) { Some(value) => value, None => return m.fall_back(185, 3) };
+                // 187: lt   21, [rb+6], [63]
+                let b = match address(m.rb.saturating_add(6)) { Some(addr) => addr, None => return m.fall_back(187, 2) };
+                let value = (21 < m.memory[b]) as i128;
+                m.memory[63] = value;
+                // 191: jnz  [63], 199
+                if m.memory[63] != 0 {
+                    m.pc = 199;
+                } else {
+                    m.pc = 194;
+                }
+            }
+            194 => {
+                m.num_instructions_processed += 1;
+                // 194: out  [187]
+                let value = m.memory[187];
+                m.pc = 196;
+                return RunResult::Output(value);
+            }
+            196 => {
+                m.num_instructions_processed += 1;
+                // 196: jz   0, 203
+                m.pc = 203;
+            }
+            199 => {
+                m.num_instructions_processed += 1;
+                // 199: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(199, 1) };
+                m.memory[64] = value;
+                m.pc = 203;
+            }
+            203 => {
+                m.num_instructions_processed += 5;
+                // 203: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(203, 5) };
+                m.memory[64] = value;
+                // 207: arb  16
+                m.rb = match i128::checked_add(m.rb, 16) { Some(value) => value, None => return m.fall_back(207, 4) };
+                // 209: add  0, [rb-6], [63]
+                let b = match address(m.rb.saturating_add(-6)) { Some(addr) => addr, None => return m.fall_back(209, 3) };
+                let value = m.memory[b];
+                m.memory[63] = value;
+                // 213: eq   [63], 39, [63]
+                let value = (m.memory[63] == 39) as i128;
+                m.memory[63] = value;
+                // 217: jnz  [63], 225
+                if m.memory[63] != 0 {
+                    m.pc = 225;
+                } else {
+                    m.pc = 220;
+                }
+            }
+            220 => {
+                m.num_instructions_processed += 1;
+                // 220: out  [209]
+                let value = m.memory[209];
+                m.pc = 222;
+                return RunResult::Output(value);
+            }
+            222 => {
+                m.num_instructions_processed += 1;
+                // 222: jz   0, 229
+                m.pc = 229;
+            }
+            225 => {
+                m.num_instructions_processed += 1;
+                // 225: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(225, 1) };
+                m.memory[64] = value;
+                m.pc = 229;
+            }
+            229 => {
+                m.num_instructions_processed += 4;
+                // 229: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(229, 4) };
+                m.memory[64] = value;
+                // 233: arb  5
+                m.rb = match i128::checked_add(m.rb, 5) { Some(value) => value, None => return m.fall_back(233, 3) };
+                // 235: eq   20, [rb-7], [63]
+                let b = match address(m.rb.saturating_add(-7)) { Some(addr) => addr, None => return m.fall_back(235, 2) };
+                let value = (20 == m.memory[b]) as i128;
+                m.memory[63] = value;
+                // 239: jnz  [63], 247
+                if m.memory[63] != 0 {
+                    m.pc = 247;
+                } else {
+                    m.pc = 242;
+                }
+            }
+            242 => {
+                m.num_instructions_processed += 1;
+                // 242: out  [235]
+                let value = m.memory[235];
+                m.pc = 244;
+                return RunResult::Output(value);
+            }
+            244 => {
+                m.num_instructions_processed += 1;
+                // 244: jnz  1, 251
+                m.pc = 251;
+            }
+            247 => {
+                m.num_instructions_processed += 1;
+                // 247: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(247, 1) };
+                m.memory[64] = value;
+                m.pc = 251;
+            }
+            251 => {
+                m.num_instructions_processed += 4;
+                // 251: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(251, 4) };
+                m.memory[64] = value;
+                // 255: arb  -1
+                m.rb = match i128::checked_add(m.rb, -1) { Some(value) => value, None => return m.fall_back(255, 3) };
+                // 257: eq   36, [rb-8], [63]
+                let b = match address(m.rb.saturating_add(-8)) { Some(addr) => addr, None => return m.fall_back(257, 2) };
+                let value = (36 == m.memory[b]) as i128;
+                m.memory[63] = value;
+                // 261: jnz  [63], 267
+                if m.memory[63] != 0 {
+                    m.pc = 267;
+                } else {
+                    m.pc = 264;
+                }
+            }
+            264 => {
+                m.num_instructions_processed += 1;
+                // 264: jz   0, 273
+                m.pc = 273;
+            }
+            267 => {
+                m.num_instructions_processed += 1;
+                // 267: out  [257]
+                let value = m.memory[257];
+                m.pc = 269;
+                return RunResult::Output(value);
+            }
+            269 => {
+                m.num_instructions_processed += 1;
+                // 269: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(269, 1) };
+                m.memory[64] = value;
+                m.pc = 273;
+            }
+            273 => {
+                m.num_instructions_processed += 5;
+                // 273: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(273, 5) };
+                m.memory[64] = value;
+                // 277: arb  -13
+                m.rb = match i128::checked_add(m.rb, -13) { Some(value) => value, None => return m.fall_back(277, 4) };
+                // 279: add  [rb-1], 0, [63]
+                let a = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(279, 3) };
+                let value = m.memory[a];
+                m.memory[63] = value;
+                // 283: eq   [63], 22, [63]
+                let value = (m.memory[63] == 22) as i128;
+                m.memory[63] = value;
+                // 287: jnz  [63], 299
+                if m.memory[63] != 0 {
+                    m.pc = 299;
+                } else {
+                    m.pc = 290;
+                }
+            }
+            290 => {
+                m.num_instructions_processed += 1;
+                // 290: out  [279]
+                let value = m.memory[279];
+                m.pc = 292;
+                return RunResult::Output(value);
+            }
+            292 => {
+                m.num_instructions_processed += 2;
+                // 292: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(292, 2) };
+                m.memory[64] = value;
+                // 296: jz   0, 299
+                m.pc = 299;
+            }
+            299 => {
+                m.num_instructions_processed += 5;
+                // 299: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(299, 5) };
+                m.memory[64] = value;
+                // 303: arb  15
+                m.rb = match i128::checked_add(m.rb, 15) { Some(value) => value, None => return m.fall_back(303, 4) };
+                // 305: mul  1, [rb-8], [63]
+                let b = match address(m.rb.saturating_add(-8)) { Some(addr) => addr, None => return m.fall_back(305, 3) };
+                let value = m.memory[b];
+                m.memory[63] = value;
+                // 309: eq   [63], 20, [63]
+                let value = (m.memory[63] == 20) as i128;
+                m.memory[63] = value;
+                // 313: jnz  [63], 321
+                if m.memory[63] != 0 {
+                    m.pc = 321;
+                } else {
+                    m.pc = 316;
+                }
+            }
+            316 => {
+                m.num_instructions_processed += 1;
+                // 316: out  [305]
+                let value = m.memory[305];
+                m.pc = 318;
+                return RunResult::Output(value);
+            }
+            318 => {
+                m.num_instructions_processed += 1;
+                // 318: jz   0, 325
+                m.pc = 325;
+            }
+            321 => {
+                m.num_instructions_processed += 1;
+                // 321: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(321, 1) };
+                m.memory[64] = value;
+                m.pc = 325;
+            }
+            325 => {
+                m.num_instructions_processed += 4;
+                // 325: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(325, 4) };
+                m.memory[64] = value;
+                // 329: arb  -13
+                m.rb = match i128::checked_add(m.rb, -13) { Some(value) => value, None => return m.fall_back(329, 3) };
+                // 331: eq   40, 40, [rb+8]
+                let dst = match address(m.rb.saturating_add(8)) { Some(addr) => addr, None => return m.fall_back(331, 2) };
+                if m.is_code(dst) { return m.fall_back(331, 2) }
+                let value = 1;
+                m.memory[dst] = value;
+                // 335: jnz  [1011], 347
+                if m.memory[1011] != 0 {
+                    m.pc = 347;
+                } else {
+                    m.pc = 338;
+                }
+            }
+            338 => {
+                m.num_instructions_processed += 1;
+                // 338: out  [331]
+                let value = m.memory[331];
+                m.pc = 340;
+                return RunResult::Output(value);
+            }
+            340 => {
+                m.num_instructions_processed += 2;
+                // 340: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(340, 2) };
+                m.memory[64] = value;
+                // 344: jnz  1, 347
+                m.pc = 347;
+            }
+            347 => {
+                m.num_instructions_processed += 4;
+                // 347: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(347, 4) };
+                m.memory[64] = value;
+                // 351: arb  -2
+                m.rb = match i128::checked_add(m.rb, -2) { Some(value) => value, None => return m.fall_back(351, 3) };
+                // 353: lt   [rb+8], 24, [63]
+                let a = match address(m.rb.saturating_add(8)) { Some(addr) => addr, None => return m.fall_back(353, 2) };
+                let value = (m.memory[a] < 24) as i128;
+                m.memory[63] = value;
+                // 357: jnz  [63], 363
+                if m.memory[63] != 0 {
+                    m.pc = 363;
+                } else {
+                    m.pc = 360;
+                }
+            }
+            360 => {
+                m.num_instructions_processed += 1;
+                // 360: jnz  1, 369
+                m.pc = 369;
+            }
+            363 => {
+                m.num_instructions_processed += 1;
+                // 363: out  [353]
+                let value = m.memory[353];
+                m.pc = 365;
+                return RunResult::Output(value);
+            }
+            365 => {
+                m.num_instructions_processed += 1;
+                // 365: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(365, 1) };
+                m.memory[64] = value;
+                m.pc = 369;
+            }
+            369 => {
+                m.num_instructions_processed += 3;
+                // 369: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(369, 3) };
+                m.memory[64] = value;
+                // 373: arb  35
+                m.rb = match i128::checked_add(m.rb, 35) { Some(value) => value, None => return m.fall_back(373, 2) };
+                // 375: jz   0, [rb-9]
+                let b = match address(m.rb.saturating_add(-9)) { Some(addr) => addr, None => return m.fall_back(375, 1) };
+                m.pc = match address(m.memory[b]) { Some(target) => target, None => return m.fall_back(375, 1) };
+            }
+            378 => {
+                m.num_instructions_processed += 2;
+                // 378: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(378, 2) };
+                m.memory[64] = value;
+                // 382: jz   0, 387
+                m.pc = 387;
+            }
+            385 => {
+                m.num_instructions_processed += 1;
+                // 385: out  [375]
+                let value = m.memory[375];
+                m.pc = 387;
+                return RunResult::Output(value);
+            }
+            387 => {
+                m.num_instructions_processed += 5;
+                // 387: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(387, 5) };
+                m.memory[64] = value;
+                // 391: arb  -26
+                m.rb = match i128::checked_add(m.rb, -26) { Some(value) => value, None => return m.fall_back(391, 4) };
+                // 393: mul  41, 1, [rb+3]
+                let dst = match address(m.rb.saturating_add(3)) { Some(addr) => addr, None => return m.fall_back(393, 3) };
+                if m.is_code(dst) { return m.fall_back(393, 3) }
+                let value = 41;
+                m.memory[dst] = value;
+                // 397: eq   [1013], 41, [63]
+                let value = (m.memory[1013] == 41) as i128;
+                m.memory[63] = value;
+                // 401: jnz  [63], 409
+                if m.memory[63] != 0 {
+                    m.pc = 409;
+                } else {
+                    m.pc = 404;
+                }
+            }
+            404 => {
+                m.num_instructions_processed += 1;
+                // 404: out  [393]
+                let value = m.memory[393];
+                m.pc = 406;
+                return RunResult::Output(value);
+            }
+            406 => {
+                m.num_instructions_processed += 1;
+                // 406: jz   0, 413
+                m.pc = 413;
+            }
+            409 => {
+                m.num_instructions_processed += 1;
+                // 409: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(409, 1) };
+                m.memory[64] = value;
+                m.pc = 413;
+            }
+            413 => {
+                m.num_instructions_processed += 5;
+                // 413: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(413, 5) };
+                m.memory[64] = value;
+                // 417: arb  2
+                m.rb = match i128::checked_add(m.rb, 2) { Some(value) => value, None => return m.fall_back(417, 4) };
+                // 419: mul  [rb-6], 1, [63]
+                let a = match address(m.rb.saturating_add(-6)) { Some(addr) => addr, None => return m.fall_back(419, 3) };
+                let value = m.memory[a];
+                m.memory[63] = value;
+                // 423: eq   [63], 36, [63]
+                let value = (m.memory[63] == 36) as i128;
+                m.memory[63] = value;
+                // 427: jnz  [63], 433
+                if m.memory[63] != 0 {
+                    m.pc = 433;
+                } else {
+                    m.pc = 430;
+                }
+            }
+            430 => {
+                m.num_instructions_processed += 1;
+                // 430: jz   0, 439
+                m.pc = 439;
+            }
+            433 => {
+                m.num_instructions_processed += 1;
+                // 433: out  [419]
+                let value = m.memory[419];
+                m.pc = 435;
+                return RunResult::Output(value);
+            }
+            435 => {
+                m.num_instructions_processed += 1;
+                // 435: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(435, 1) };
+                m.memory[64] = value;
+                m.pc = 439;
+            }
+            439 => {
+                m.num_instructions_processed += 5;
+                // 439: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(439, 5) };
+                m.memory[64] = value;
+                // 443: arb  -3
+                m.rb = match i128::checked_add(m.rb, -3) { Some(value) => value, None => return m.fall_back(443, 4) };
+                // 445: mul  42, 1, [rb+10]
+                let dst = match address(m.rb.saturating_add(10)) { Some(addr) => addr, None => return m.fall_back(445, 3) };
+                if m.is_code(dst) { return m.fall_back(445, 3) }
+                let value = 42;
+                m.memory[dst] = value;
+                // 449: eq   [1019], 40, [63]
+                let value = (m.memory[1019] == 40) as i128;
+                m.memory[63] = value;
+                // 453: jnz  [63], 463
+                if m.memory[63] != 0 {
+                    m.pc = 463;
+                } else {
+                    m.pc = 456;
+                }
+            }
+            456 => {
+                m.num_instructions_processed += 2;
+                // 456: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(456, 2) };
+                m.memory[64] = value;
+                // 460: jz   0, 465
+                m.pc = 465;
+            }
+            463 => {
+                m.num_instructions_processed += 1;
+                // 463: out  [445]
+                let value = m.memory[445];
+                m.pc = 465;
+                return RunResult::Output(value);
+            }
+            465 => {
+                m.num_instructions_processed += 3;
+                // 465: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(465, 3) };
+                m.memory[64] = value;
+                // 469: arb  15
+                m.rb = match i128::checked_add(m.rb, 15) { Some(value) => value, None => return m.fall_back(469, 2) };
+                // 471: jnz  1, [rb-1]
+                let b = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(471, 1) };
+                m.pc = match address(m.memory[b]) { Some(target) => target, None => return m.fall_back(471, 1) };
+            }
+            474 => {
+                m.num_instructions_processed += 2;
+                // 474: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(474, 2) };
+                m.memory[64] = value;
+                // 478: jz   0, 483
+                m.pc = 483;
+            }
+            481 => {
+                m.num_instructions_processed += 1;
+                // 481: out  [471]
+                let value = m.memory[471];
+                m.pc = 483;
+                return RunResult::Output(value);
+            }
+            483 => {
+                m.num_instructions_processed += 4;
+                // 483: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(483, 4) };
+                m.memory[64] = value;
+                // 487: arb  -27
+                m.rb = match i128::checked_add(m.rb, -27) { Some(value) => value, None => return m.fall_back(487, 3) };
+                // 489: lt   [rb+3], 23, [63]
+                let a = match address(m.rb.saturating_add(3)) { Some(addr) => addr, None => return m.fall_back(489, 2) };
+                let value = (m.memory[a] < 23) as i128;
+                m.memory[63] = value;
+                // 493: jnz  [63], 505
+                if m.memory[63] != 0 {
+                    m.pc = 505;
+                } else {
+                    m.pc = 496;
+                }
+            }
+            496 => {
+                m.num_instructions_processed += 1;
+                // 496: out  [489]
+                let value = m.memory[489];
+                m.pc = 498;
+                return RunResult::Output(value);
+            }
+            498 => {
+                m.num_instructions_processed += 2;
+                // 498: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(498, 2) };
+                m.memory[64] = value;
+                // 502: jnz  1, 505
+                m.pc = 505;
+            }
+            505 => {
+                m.num_instructions_processed += 5;
+                // 505: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(505, 5) };
+                m.memory[64] = value;
+                // 509: arb  13
+                m.rb = match i128::checked_add(m.rb, 13) { Some(value) => value, None => return m.fall_back(509, 4) };
+                // 511: mul  1, [rb-9], [63]
+                let b = match address(m.rb.saturating_add(-9)) { Some(addr) => addr, None => return m.fall_back(511, 3) };
+                let value = m.memory[b];
+                m.memory[63] = value;
+                // 515: eq   [63], 28, [63]
+                let value = (m.memory[63] == 28) as i128;
+                m.memory[63] = value;
+                // 519: jnz  [63], 525
+                if m.memory[63] != 0 {
+                    m.pc = 525;
+                } else {
+                    m.pc = 522;
+                }
+            }
+            522 => {
+                m.num_instructions_processed += 1;
+                // 522: jnz  1, 531
+                m.pc = 531;
+            }
+            525 => {
+                m.num_instructions_processed += 1;
+                // 525: out  [511]
+                let value = m.memory[511];
+                m.pc = 527;
+                return RunResult::Output(value);
+            }
+            527 => {
+                m.num_instructions_processed += 1;
+                // 527: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(527, 1) };
+                m.memory[64] = value;
+                m.pc = 531;
+            }
+            531 => {
+                m.num_instructions_processed += 5;
+                // 531: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(531, 5) };
+                m.memory[64] = value;
+                // 535: arb  1
+                m.rb = match i128::checked_add(m.rb, 1) { Some(value) => value, None => return m.fall_back(535, 4) };
+                // 537: add  0, [rb-8], [63]
+                let b = match address(m.rb.saturating_add(-8)) { Some(addr) => addr, None => return m.fall_back(537, 3) };
+                let value = m.memory[b];
+                m.memory[63] = value;
+                // 541: eq   [63], 35, [63]
+                let value = (m.memory[63] == 35) as i128;
+                m.memory[63] = value;
+                // 545: jnz  [63], 551
+                if m.memory[63] != 0 {
+                    m.pc = 551;
+                } else {
+                    m.pc = 548;
+                }
+            }
+            548 => {
+                m.num_instructions_processed += 1;
+                // 548: jnz  1, 557
+                m.pc = 557;
+            }
+            551 => {
+                m.num_instructions_processed += 1;
+                // 551: out  [537]
+                let value = m.memory[537];
+                m.pc = 553;
+                return RunResult::Output(value);
+            }
+            553 => {
+                m.num_instructions_processed += 1;
+                // 553: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(553, 1) };
+                m.memory[64] = value;
+                m.pc = 557;
+            }
+            557 => {
+                m.num_instructions_processed += 4;
+                // 557: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(557, 4) };
+                m.memory[64] = value;
+                // 561: arb  6
+                m.rb = match i128::checked_add(m.rb, 6) { Some(value) => value, None => return m.fall_back(561, 3) };
+                // 563: lt   43, 44, [rb-4]
+                let dst = match address(m.rb.saturating_add(-4)) { Some(addr) => addr, None => return m.fall_back(563, 2) };
+                if m.is_code(dst) { return m.fall_back(563, 2) }
+                let value = 1;
+                m.memory[dst] = value;
+                // 567: jnz  [1013], 575
+                if m.memory[1013] != 0 {
+                    m.pc = 575;
+                } else {
+                    m.pc = 570;
+                }
+            }
+            570 => {
+                m.num_instructions_processed += 1;
+                // 570: out  [563]
+                let value = m.memory[563];
+                m.pc = 572;
+                return RunResult::Output(value);
+            }
+            572 => {
+                m.num_instructions_processed += 1;
+                // 572: jz   0, 579
+                m.pc = 579;
+            }
+            575 => {
+                m.num_instructions_processed += 1;
+                // 575: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(575, 1) };
+                m.memory[64] = value;
+                m.pc = 579;
+            }
+            579 => {
+                m.num_instructions_processed += 5;
+                // 579: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(579, 5) };
+                m.memory[64] = value;
+                // 583: arb  -9
+                m.rb = match i128::checked_add(m.rb, -9) { Some(value) => value, None => return m.fall_back(583, 4) };
+                // 585: add  [rb-4], 0, [63]
+                let a = match address(m.rb.saturating_add(-4)) { Some(addr) => addr, None => return m.fall_back(585, 3) };
+                let value = m.memory[a];
+                m.memory[63] = value;
+                // 589: eq   [63], 40, [63]
+                let value = (m.memory[63] == 40) as i128;
+                m.memory[63] = value;
+                // 593: jnz  [63], 599
+                if m.memory[63] != 0 {
+                    m.pc = 599;
+                } else {
+                    m.pc = 596;
+                }
+            }
+            596 => {
+                m.num_instructions_processed += 1;
+                // 596: jnz  1, 605
+                m.pc = 605;
+            }
+            599 => {
+                m.num_instructions_processed += 1;
+                // 599: out  [585]
+                let value = m.memory[585];
+                m.pc = 601;
+                return RunResult::Output(value);
+            }
+            601 => {
+                m.num_instructions_processed += 1;
+                // 601: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(601, 1) };
+                m.memory[64] = value;
+                m.pc = 605;
+            }
+            605 => {
+                m.num_instructions_processed += 3;
+                // 605: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(605, 3) };
+                m.memory[64] = value;
+                // 609: arb  12
+                m.rb = match i128::checked_add(m.rb, 12) { Some(value) => value, None => return m.fall_back(609, 2) };
+                // 611: jz   [rb+1], 621
+                let a = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(611, 1) };
+                if m.memory[a] == 0 {
+                    m.pc = 621;
+                } else {
+                    m.pc = 614;
+                }
+            }
+            614 => {
+                m.num_instructions_processed += 2;
+                // 614: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(614, 2) };
+                m.memory[64] = value;
+                // 618: jz   0, 623
+                m.pc = 623;
+            }
+            621 => {
+                m.num_instructions_processed += 1;
+                // 621: out  [611]
+                let value = m.memory[611];
+                m.pc = 623;
+                return RunResult::Output(value);
+            }
+            623 => {
+                m.num_instructions_processed += 5;
+                // 623: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(623, 5) };
+                m.memory[64] = value;
+                // 627: arb  -22
+                m.rb = match i128::checked_add(m.rb, -22) { Some(value) => value, None => return m.fall_back(627, 4) };
+                // 629: mul  [rb+9], 1, [63]
+                let a = match address(m.rb.saturating_add(9)) { Some(addr) => addr, None => return m.fall_back(629, 3) };
+                let value = m.memory[a];
+                m.memory[63] = value;
+                // 633: eq   [63], 23, [63]
+                let value = (m.memory[63] == 23) as i128;
+                m.memory[63] = value;
+                // 637: jnz  [63], 649
+                if m.memory[63] != 0 {
+                    m.pc = 649;
+                } else {
+                    m.pc = 640;
+                }
+            }
+            640 => {
+                m.num_instructions_processed += 1;
+                // 640: out  [629]
+                let value = m.memory[629];
+                m.pc = 642;
+                return RunResult::Output(value);
+            }
+            642 => {
+                m.num_instructions_processed += 2;
+                // 642: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(642, 2) };
+                m.memory[64] = value;
+                // 646: jnz  1, 649
+                m.pc = 649;
+            }
+            649 => {
+                m.num_instructions_processed += 3;
+                // 649: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(649, 3) };
+                m.memory[64] = value;
+                // 653: arb  17
+                m.rb = match i128::checked_add(m.rb, 17) { Some(value) => value, None => return m.fall_back(653, 2) };
+                // 655: jz   [rb+5], 667
+                let a = match address(m.rb.saturating_add(5)) { Some(addr) => addr, None => return m.fall_back(655, 1) };
+                if m.memory[a] == 0 {
+                    m.pc = 667;
+                } else {
+                    m.pc = 658;
+                }
+            }
+            658 => {
+                m.num_instructions_processed += 1;
+                // 658: out  [655]
+                let value = m.memory[655];
+                m.pc = 660;
+                return RunResult::Output(value);
+            }
+            660 => {
+                m.num_instructions_processed += 2;
+                // 660: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(660, 2) };
+                m.memory[64] = value;
+                // 664: jz   0, 667
+                m.pc = 667;
+            }
+            667 => {
+                m.num_instructions_processed += 3;
+                // 667: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(667, 3) };
+                m.memory[64] = value;
+                // 671: arb  -3
+                m.rb = match i128::checked_add(m.rb, -3) { Some(value) => value, None => return m.fall_back(671, 2) };
+                // 673: jnz  [rb+9], 685
+                let a = match address(m.rb.saturating_add(9)) { Some(addr) => addr, None => return m.fall_back(673, 1) };
+                if m.memory[a] != 0 {
+                    m.pc = 685;
+                } else {
+                    m.pc = 676;
+                }
+            }
+            676 => {
+                m.num_instructions_processed += 1;
+                // 676: out  [673]
+                let value = m.memory[673];
+                m.pc = 678;
+                return RunResult::Output(value);
+            }
+            678 => {
+                m.num_instructions_processed += 2;
+                // 678: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(678, 2) };
+                m.memory[64] = value;
+                // 682: jz   0, 685
+                m.pc = 685;
+            }
+            685 => {
+                m.num_instructions_processed += 4;
+                // 685: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(685, 4) };
+                m.memory[64] = value;
+                // 689: arb  3
+                m.rb = match i128::checked_add(m.rb, 3) { Some(value) => value, None => return m.fall_back(689, 3) };
+                // 691: eq   [rb-9], 37, [63]
+                let a = match address(m.rb.saturating_add(-9)) { Some(addr) => addr, None => return m.fall_back(691, 2) };
+                let value = (m.memory[a] == 37) as i128;
+                m.memory[63] = value;
+                // 695: jnz  [63], 707
+                if m.memory[63] != 0 {
+                    m.pc = 707;
+                } else {
+                    m.pc = 698;
+                }
+            }
+            698 => {
+                m.num_instructions_processed += 1;
+                // 698: out  [691]
+                let value = m.memory[691];
+                m.pc = 700;
+                return RunResult::Output(value);
+            }
+            700 => {
+                m.num_instructions_processed += 2;
+                // 700: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(700, 2) };
+                m.memory[64] = value;
+                // 704: jnz  1, 707
+                m.pc = 707;
+            }
+            707 => {
+                m.num_instructions_processed += 3;
+                // 707: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(707, 3) };
+                m.memory[64] = value;
+                // 711: arb  7
+                m.rb = match i128::checked_add(m.rb, 7) { Some(value) => value, None => return m.fall_back(711, 2) };
+                // 713: jnz  [rb-2], 723
+                let a = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(713, 1) };
+                if m.memory[a] != 0 {
+                    m.pc = 723;
+                } else {
+                    m.pc = 716;
+                }
+            }
+            716 => {
+                m.num_instructions_processed += 2;
+                // 716: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(716, 2) };
+                m.memory[64] = value;
+                // 720: jz   0, 725
+                m.pc = 725;
+            }
+            723 => {
+                m.num_instructions_processed += 1;
+                // 723: out  [713]
+                let value = m.memory[713];
+                m.pc = 725;
+                return RunResult::Output(value);
+            }
+            725 => {
+                m.num_instructions_processed += 5;
+                // 725: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(725, 5) };
+                m.memory[64] = value;
+                // 729: arb  -15
+                m.rb = match i128::checked_add(m.rb, -15) { Some(value) => value, None => return m.fall_back(729, 4) };
+                // 731: add  44, 0, [rb+8]
+                let dst = match address(m.rb.saturating_add(8)) { Some(addr) => addr, None => return m.fall_back(731, 3) };
+                if m.is_code(dst) { return m.fall_back(731, 3) }
+                let value = 44;
+                m.memory[dst] = value;
+                // 735: eq   [1015], 45, [63]
+                let value = (m.memory[1015] == 45) as i128;
+                m.memory[63] = value;
+                // 739: jnz  [63], 745
+                if m.memory[63] != 0 {
+                    m.pc = 745;
+                } else {
+                    m.pc = 742;
+                }
+            }
+            742 => {
+                m.num_instructions_processed += 1;
+                // 742: jnz  1, 751
+                m.pc = 751;
+            }
+            745 => {
+                m.num_instructions_processed += 1;
+                // 745: out  [731]
+                let value = m.memory[731];
+                m.pc = 747;
+                return RunResult::Output(value);
+            }
+            747 => {
+                m.num_instructions_processed += 1;
+                // 747: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(747, 1) };
+                m.memory[64] = value;
+                m.pc = 751;
+            }
+            751 => {
+                m.num_instructions_processed += 3;
+                // 751: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(751, 3) };
+                m.memory[64] = value;
+                // 755: arb  28
+                m.rb = match i128::checked_add(m.rb, 28) { Some(value) => value, None => return m.fall_back(755, 2) };
+                // 757: jz   0, [rb-7]
+                let b = match address(m.rb.saturating_add(-7)) { Some(addr) => addr, None => return m.fall_back(757, 1) };
+                m.pc = match address(m.memory[b]) { Some(target) => target, None => return m.fall_back(757, 1) };
+            }
+            760 => {
+                m.num_instructions_processed += 1;
+                // 760: out  [757]
+                let value = m.memory[757];
+                m.pc = 762;
+                return RunResult::Output(value);
+            }
+            762 => {
+                m.num_instructions_processed += 2;
+                // 762: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(762, 2) };
+                m.memory[64] = value;
+                // 766: jz   0, 769
+                m.pc = 769;
+            }
+            769 => {
+                m.num_instructions_processed += 5;
+                // 769: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(769, 5) };
+                m.memory[64] = value;
+                // 773: arb  -12
+                m.rb = match i128::checked_add(m.rb, -12) { Some(value) => value, None => return m.fall_back(773, 4) };
+                // 775: add  45, 0, [rb-5]
+                let dst = match address(m.rb.saturating_add(-5)) { Some(addr) => addr, None => return m.fall_back(775, 3) };
+                if m.is_code(dst) { return m.fall_back(775, 3) }
+                let value = 45;
+                m.memory[dst] = value;
+                // 779: eq   [1018], 45, [63]
+                let value = (m.memory[1018] == 45) as i128;
+                m.memory[63] = value;
+                // 783: jnz  [63], 791
+                if m.memory[63] != 0 {
+                    m.pc = 791;
+                } else {
+                    m.pc = 786;
+                }
+            }
+            786 => {
+                m.num_instructions_processed += 1;
+                // 786: out  [775]
+                let value = m.memory[775];
+                m.pc = 788;
+                return RunResult::Output(value);
+            }
+            788 => {
+                m.num_instructions_processed += 1;
+                // 788: jnz  1, 795
+                m.pc = 795;
+            }
+            791 => {
+                m.num_instructions_processed += 1;
+                // 791: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(791, 1) };
+                m.memory[64] = value;
+                m.pc = 795;
+            }
+            795 => {
+                m.num_instructions_processed += 4;
+                // 795: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(795, 4) };
+                m.memory[64] = value;
+                // 799: arb  -9
+                m.rb = match i128::checked_add(m.rb, -9) { Some(value) => value, None => return m.fall_back(799, 3) };
+                // 801: lt   26, [rb-5], [63]
+                let b = match address(m.rb.saturating_add(-5)) { Some(addr) => addr, None => return m.fall_back(801, 2) };
+                let value = (26 < m.memory[b]) as i128;
+                m.memory[63] = value;
+                // 805: jnz  [63], 815
+                if m.memory[63] != 0 {
+                    m.pc = 815;
+                } else {
+                    m.pc = 808;
+                }
+            }
+            808 => {
+                m.num_instructions_processed += 2;
+                // 808: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(808, 2) };
+                m.memory[64] = value;
+                // 812: jz   0, 817
+                m.pc = 817;
+            }
+            815 => {
+                m.num_instructions_processed += 1;
+                // 815: out  [801]
+                let value = m.memory[801];
+                m.pc = 817;
+                return RunResult::Output(value);
+            }
+            817 => {
+                m.num_instructions_processed += 4;
+                // 817: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(817, 4) };
+                m.memory[64] = value;
+                // 821: arb  -1
+                m.rb = match i128::checked_add(m.rb, -1) { Some(value) => value, None => return m.fall_back(821, 3) };
+                // 823: lt   46, 45, [rb-3]
+                let dst = match address(m.rb.saturating_add(-3)) { Some(addr) => addr, None => return m.fall_back(823, 2) };
+                if m.is_code(dst) { return m.fall_back(823, 2) }
+                let value = 0;
+                m.memory[dst] = value;
+                // 827: jnz  [1010], 833
+                if m.memory[1010] != 0 {
+                    m.pc = 833;
+                } else {
+                    m.pc = 830;
+                }
+            }
+            830 => {
+                m.num_instructions_processed += 1;
+                // 830: jnz  1, 839
+                m.pc = 839;
+            }
+            833 => {
+                m.num_instructions_processed += 1;
+                // 833: out  [823]
+                let value = m.memory[823];
+                m.pc = 835;
+                return RunResult::Output(value);
+            }
+            835 => {
+                m.num_instructions_processed += 1;
+                // 835: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(835, 1) };
+                m.memory[64] = value;
+                m.pc = 839;
+            }
+            839 => {
+                m.num_instructions_processed += 3;
+                // 839: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(839, 3) };
+                m.memory[64] = value;
+                // 843: arb  3
+                m.rb = match i128::checked_add(m.rb, 3) { Some(value) => value, None => return m.fall_back(843, 2) };
+                // 845: jnz  1, [rb+8]
+                let b = match address(m.rb.saturating_add(8)) { Some(addr) => addr, None => return m.fall_back(845, 1) };
+                m.pc = match address(m.memory[b]) { Some(target) => target, None => return m.fall_back(845, 1) };
+            }
+            848 => {
+                m.num_instructions_processed += 1;
+                // 848: out  [845]
+                let value = m.memory[845];
+                m.pc = 850;
+                return RunResult::Output(value);
+            }
+            850 => {
+                m.num_instructions_processed += 2;
+                // 850: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(850, 2) };
+                m.memory[64] = value;
+                // 854: jz   0, 857
+                m.pc = 857;
+            }
+            857 => {
+                m.num_instructions_processed += 4;
+                // 857: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(857, 4) };
+                m.memory[64] = value;
+                // 861: arb  -9
+                m.rb = match i128::checked_add(m.rb, -9) { Some(value) => value, None => return m.fall_back(861, 3) };
+                // 863: eq   [rb-4], 37, [63]
+                let a = match address(m.rb.saturating_add(-4)) { Some(addr) => addr, None => return m.fall_back(863, 2) };
+                let value = (m.memory[a] == 37) as i128;
+                m.memory[63] = value;
+                // 867: jnz  [63], 877
+                if m.memory[63] != 0 {
+                    m.pc = 877;
+                } else {
+                    m.pc = 870;
+                }
+            }
+            870 => {
+                m.num_instructions_processed += 2;
+                // 870: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(870, 2) };
+                m.memory[64] = value;
+                // 874: jnz  1, 879
+                m.pc = 879;
+            }
+            877 => {
+                m.num_instructions_processed += 1;
+                // 877: out  [863]
+                let value = m.memory[863];
+                m.pc = 879;
+                return RunResult::Output(value);
+            }
+            879 => {
+                m.num_instructions_processed += 4;
+                // 879: mul  [64], 2, [64]
+                let value = match i128::checked_mul(m.memory[64], 2) { Some(value) => value, None => return m.fall_back(879, 4) };
+                m.memory[64] = value;
+                // 883: arb  8
+                m.rb = match i128::checked_add(m.rb, 8) { Some(value) => value, None => return m.fall_back(883, 3) };
+                // 885: eq   47, 46, [rb+2]
+                let dst = match address(m.rb.saturating_add(2)) { Some(addr) => addr, None => return m.fall_back(885, 2) };
+                if m.is_code(dst) { return m.fall_back(885, 2) }
+                let value = 0;
+                m.memory[dst] = value;
+                // 889: jnz  [1017], 895
+                if m.memory[1017] != 0 {
+                    m.pc = 895;
+                } else {
+                    m.pc = 892;
+                }
+            }
+            892 => {
+                m.num_instructions_processed += 1;
+                // 892: jz   0, 901
+                m.pc = 901;
+            }
+            895 => {
+                m.num_instructions_processed += 1;
+                // 895: out  [885]
+                let value = m.memory[885];
+                m.pc = 897;
+                return RunResult::Output(value);
+            }
+            897 => {
+                m.num_instructions_processed += 1;
+                // 897: add  [64], 1, [64]
+                let value = match i128::checked_add(m.memory[64], 1) { Some(value) => value, None => return m.fall_back(897, 1) };
+                m.memory[64] = value;
+                m.pc = 901;
+            }
+            901 => {
+                m.num_instructions_processed += 1;
+                // 901: out  [64]
+                let value = m.memory[64];
+                m.pc = 903;
+                return RunResult::Output(value);
+            }
+            903 => {
+                m.num_instructions_processed += 1;
+                // 903: halt
+                m.pc = 903;
+                return RunResult::Halt;
+            }
+            904 => {
+                m.num_instructions_processed += 3;
+                // 904: mul  1, 27, [rb+1]
+                let dst = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(904, 3) };
+                if m.is_code(dst) { return m.fall_back(904, 3) }
+                let value = 27;
+                m.memory[dst] = value;
+                // 908: mul  1, 915, [rb+0]
+                let dst = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(908, 2) };
+                if m.is_code(dst) { return m.fall_back(908, 2) }
+                let value = 915;
+                m.memory[dst] = value;
+                // 912: jz   0, 922
+                m.pc = 922;
+            }
+            915 => {
+                m.num_instructions_processed += 2;
+                // 915: add  [rb+1], 14429, [rb+1]
+                let a = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(915, 2) };
+                let dst = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(915, 2) };
+                if m.is_code(dst) { return m.fall_back(915, 2) }
+                let value = match i128::checked_add(m.memory[a], 14429) { Some(value) => value, None => return m.fall_back(915, 2) };
+                m.memory[dst] = value;
+                // 919: out  [rb+1]
+                let a = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(919, 1) };
+                let value = m.memory[a];
+                m.pc = 921;
+                return RunResult::Output(value);
+            }
+            921 => {
+                m.num_instructions_processed += 1;
+                // 921: halt
+                m.pc = 921;
+                return RunResult::Halt;
+            }
+            922 => {
+                m.num_instructions_processed += 3;
+                // 922: arb  3
+                m.rb = match i128::checked_add(m.rb, 3) { Some(value) => value, None => return m.fall_back(922, 3) };
+                // 924: lt   [rb-2], 3, [63]
+                let a = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(924, 2) };
+                let value = (m.memory[a] < 3) as i128;
+                m.memory[63] = value;
+                // 928: jnz  [63], 964
+                if m.memory[63] != 0 {
+                    m.pc = 964;
+                } else {
+                    m.pc = 931;
+                }
+            }
+            931 => {
+                m.num_instructions_processed += 3;
+                // 931: add  [rb-2], -1, [rb+1]
+                let a = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(931, 3) };
+                let dst = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(931, 3) };
+                if m.is_code(dst) { return m.fall_back(931, 3) }
+                let value = match i128::checked_add(m.memory[a], -1) { Some(value) => value, None => return m.fall_back(931, 3) };
+                m.memory[dst] = value;
+                // 935: mul  1, 942, [rb+0]
+                let dst = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(935, 2) };
+                if m.is_code(dst) { return m.fall_back(935, 2) }
+                let value = 942;
+                m.memory[dst] = value;
+                // 939: jnz  1, 922
+                m.pc = 922;
+            }
+            942 => {
+                m.num_instructions_processed += 4;
+                // 942: mul  [rb+1], 1, [rb-1]
+                let a = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(942, 4) };
+                let dst = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(942, 4) };
+                if m.is_code(dst) { return m.fall_back(942, 4) }
+                let value = m.memory[a];
+                m.memory[dst] = value;
+                // 946: add  [rb-2], -3, [rb+1]
+                let a = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(946, 3) };
+                let dst = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(946, 3) };
+                if m.is_code(dst) { return m.fall_back(946, 3) }
+                let value = match i128::checked_add(m.memory[a], -3) { Some(value) => value, None => return m.fall_back(946, 3) };
+                m.memory[dst] = value;
+                // 950: add  957, 0, [rb+0]
+                let dst = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(950, 2) };
+                if m.is_code(dst) { return m.fall_back(950, 2) }
+                let value = 957;
+                m.memory[dst] = value;
+                // 954: jz   0, 922
+                m.pc = 922;
+            }
+            957 => {
+                m.num_instructions_processed += 2;
+                // 957: add  [rb+1], [rb-1], [rb-2]
+                let a = match address(m.rb.saturating_add(1)) { Some(addr) => addr, None => return m.fall_back(957, 2) };
+                let b = match address(m.rb.saturating_add(-1)) { Some(addr) => addr, None => return m.fall_back(957, 2) };
+                let dst = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(957, 2) };
+                if m.is_code(dst) { return m.fall_back(957, 2) }
+                let value = match i128::checked_add(m.memory[a], m.memory[b]) { Some(value) => value, None => return m.fall_back(957, 2) };
+                m.memory[dst] = value;
+                // 961: jnz  1, 968
+                m.pc = 968;
+            }
+            964 => {
+                m.num_instructions_processed += 1;
+                // 964: add  [rb-2], 0, [rb-2]
+                let a = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(964, 1) };
+                let dst = match address(m.rb.saturating_add(-2)) { Some(addr) => addr, None => return m.fall_back(964, 1) };
+                if m.is_code(dst) { return m.fall_back(964, 1) }
+                let value = m.memory[a];
+                m.memory[dst] = value;
+                m.pc = 968;
+            }
+            968 => {
+                m.num_instructions_processed += 2;
+                // 968: arb  -3
+                m.rb = match i128::checked_add(m.rb, -3) { Some(value) => value, None => return m.fall_back(968, 2) };
+                // 970: jnz  1, [rb+0]
+                let b = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(970, 1) };
+                m.pc = match address(m.memory[b]) { Some(target) => target, None => return m.fall_back(970, 1) };
+            }
+            _ => return m.fall_back(m.pc, 0),
+        }
+    }
+}
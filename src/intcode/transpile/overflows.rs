@@ -0,0 +1,68 @@
+// generated by `transpile`, see intcode::transpile. regenerate it rather than editing it
+
+use super::{Compiled, Machine};
+use crate::intcode::RunResult;
+
+pub static PROGRAM: Compiled = Compiled {
+    proggy: &[
+        3, 21, 3, 22, 1, 21, 22, 23, 4, 23, 2, 21, 22, 24, 4, 24, 9, 24, 9, 24, 99, 0, 0, 0, 0,
+    ],
+    code: &[
+        0x1fffff,
+    ],
+    run,
+};
+
+fn run(m: &mut Machine) -> RunResult {
+    loop {
+        match m.pc {
+            0 => {
+                m.num_instructions_processed += 1;
+                // 0: in   [21]
+                let input = match m.input.pop_back() {
+                    Some(input) => input,
+                    None => { m.num_instructions_processed -= 1; return RunResult::NeedMoreInput; }
+                };
+                m.memory[21] = input;
+                m.pc = 2;
+            }
+            2 => {
+                m.num_instructions_processed += 3;
+                // 2: in   [22]
+                let input = match m.input.pop_back() {
+                    Some(input) => input,
+                    None => { m.num_instructions_processed -= 3; return RunResult::NeedMoreInput; }
+                };
+                m.memory[22] = input;
+                // 4: add  [21], [22], [23]
+                let value = match i128::checked_add(m.memory[21], m.memory[22]) { Some(value) => value, None => return m.fall_back(4, 2) };
+                m.memory[23] = value;
+                // 8: out  [23]
+                let value = m.memory[23];
+                m.pc = 10;
+                return RunResult::Output(value);
+            }
+            10 => {
+                m.num_instructions_processed += 2;
+                // 10: mul  [21], [22], [24]
+                let value = match i128::checked_mul(m.memory[21], m.memory[22]) { Some(value) => value, None => return m.fall_back(10, 2) };
+                m.memory[24] = value;
+                // 14: out  [24]
+                let value = m.memory[24];
+                m.pc = 16;
+                return RunResult::Output(value);
+            }
+            16 => {
+                m.num_instructions_processed += 3;
+                // 16: arb  [24]
+                m.rb = match i128::checked_add(m.rb, m.memory[24]) { Some(value) => value, None => return m.fall_back(16, 3) };
+                // 18: arb  [24]
+                m.rb = match i128::checked_add(m.rb, m.memory[24]) { Some(value) => value, None => return m.fall_back(18, 2) };
+                // 20: halt
+                m.pc = 20;
+                return RunResult::Halt;
+            }
+            _ => return m.fall_back(m.pc, 0),
+        }
+    }
+}
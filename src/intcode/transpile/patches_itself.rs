@@ -0,0 +1,49 @@
+// generated by `transpile`, see intcode::transpile. regenerate it rather than editing it
+
+use super::{address, Compiled, Machine};
+use crate::intcode::RunResult;
+
+pub static PROGRAM: Compiled = Compiled {
+    proggy: &[
+        109, 5, 203, 0, 104, 0, 99,
+    ],
+    code: &[
+        0x7f,
+    ],
+    run,
+};
+
+fn run(m: &mut Machine) -> RunResult {
+    loop {
+        match m.pc {
+            0 => {
+                m.num_instructions_processed += 1;
+                // 0: arb  5
+                m.rb = match i128::checked_add(m.rb, 5) { Some(value) => value, None => return m.fall_back(0, 1) };
+                m.pc = 2;
+            }
+            2 => {
+                m.num_instructions_processed += 2;
+                // 2: in   [rb+0]
+                let dst = match address(m.rb) { Some(addr) => addr, None => return m.fall_back(2, 2) };
+                if m.is_code(dst) { return m.fall_back(2, 2) }
+                let input = match m.input.pop_back() {
+                    Some(input) => input,
+                    None => { m.num_instructions_processed -= 2; return RunResult::NeedMoreInput; }
+                };
+                m.memory[dst] = input;
+                // 4: out  0
+                let value = 0;
+                m.pc = 6;
+                return RunResult::Output(value);
+            }
+            6 => {
+                m.num_instructions_processed += 1;
+                // 6: halt
+                m.pc = 6;
+                return RunResult::Halt;
+            }
+            _ => return m.fall_back(m.pc, 0),
+        }
+    }
+}